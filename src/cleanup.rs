@@ -0,0 +1,187 @@
+use crate::{
+    commands::CleanupOptions,
+    error::PargitError,
+    output::{self, StaleBranchReport},
+    pargit::Pargit,
+    repo::{MergedBranch, Repository},
+    utils::{can_ask_questions, get_color_theme, ObjectKind},
+};
+
+use anyhow::{bail, Context, Result};
+use dialoguer::Confirm;
+use git2::BranchType;
+use log::{info, warn};
+use std::{
+    collections::BTreeMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use strum::IntoEnumIterator;
+
+const REMOTE: &str = "origin";
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Prunes remote-tracking branches deleted from the remote, deletes local branches (and with `--remote`, feature and
+/// bugfix branches on the remote) already merged into the remote develop branch, pulls the develop branch and reports
+/// releases and hotfixes left unfinished
+pub fn run(project: &Pargit, options: &CleanupOptions) -> Result<()> {
+    let config = project.config();
+    let repo = project.repo();
+    let develop_branch_name = &config.develop_branch_name;
+    repo.git_fetch(REMOTE)?;
+    let remote_develop = repo
+        .branch_upstream_name(develop_branch_name)?
+        .with_context(|| format!("Branch {develop_branch_name} does not track a remote branch"))?;
+
+    let stale = repo.stale_remote_branches(REMOTE)?;
+    for name in &stale {
+        info!("Remote-tracking branch {name} no longer exists on {REMOTE}");
+    }
+
+    let prefixes = ObjectKind::iter()
+        .map(|kind| format!("{kind}/"))
+        .collect::<Vec<_>>();
+    let merged = repo.find_merged_branches(
+        BranchType::Local,
+        &prefixes,
+        develop_branch_name,
+        &remote_develop,
+    )?;
+    log_merged(&merged, &remote_develop);
+
+    let merged_remote = if options.remote {
+        let prefixes = [ObjectKind::Feature, ObjectKind::Bugfix]
+            .iter()
+            .map(|kind| format!("{REMOTE}/{kind}/"))
+            .collect::<Vec<_>>();
+        let merged_remote = repo
+            .find_merged_branches(
+                BranchType::Remote,
+                &prefixes,
+                develop_branch_name,
+                &remote_develop,
+            )?
+            .into_iter()
+            .filter(|branch| !stale.contains(&branch.name))
+            .collect::<Vec<_>>();
+        log_merged(&merged_remote, &remote_develop);
+        merged_remote
+    } else {
+        Vec::new()
+    };
+
+    report_stale_releases(repo, config.stale_branch_days)?;
+
+    if options.dry_run {
+        info!("Dry run. Not deleting branches or pulling");
+        return Ok(());
+    }
+    confirm_deletion(merged.iter().chain(&merged_remote))?;
+
+    if !stale.is_empty() {
+        info!("Pruning remote-tracking branches...");
+        repo.prune_remote(REMOTE)?;
+    }
+
+    let current_branch_name = repo.current_branch_name()?;
+    for branch in &merged {
+        if current_branch_name == branch.name {
+            info!(
+                "Branch {current_branch_name} is the current branch, switching to {develop_branch_name}..."
+            );
+            repo.switch_to_branch_name(develop_branch_name)?;
+        }
+        info!("Deleting {}...", branch.name);
+        repo.delete_branch_name(&branch.name)?;
+        output::record(|report| report.deleted_branches.push(branch.name.clone()));
+    }
+
+    if !merged_remote.is_empty() {
+        let names = merged_remote
+            .iter()
+            .filter_map(|branch| branch.name.strip_prefix(&format!("{REMOTE}/")))
+            .map(String::from)
+            .collect::<Vec<_>>();
+        info!("Deleting {} from {REMOTE}...", names.join(", "));
+        repo.delete_remote_branches(REMOTE, &names)?;
+        output::record(|report| {
+            report
+                .deleted_branches
+                .extend(merged_remote.iter().map(|branch| branch.name.clone()))
+        });
+    }
+
+    if repo.is_behind_upstream(develop_branch_name)? {
+        info!("Pulling develop branch from remote (ff-only)...");
+        if repo.current_branch_name()? == *develop_branch_name {
+            repo.pull_current_branch(true)?;
+        } else {
+            repo.pull_branch_from_remote(develop_branch_name, true)?;
+        }
+    }
+    Ok(())
+}
+
+fn log_merged(merged: &[MergedBranch], remote_develop: &str) {
+    for branch in merged {
+        info!(
+            "Branch {} is {} {remote_develop}",
+            branch.name,
+            if branch.squashed {
+                "squashed or rebased onto"
+            } else {
+                "merged to"
+            }
+        );
+    }
+}
+
+fn confirm_deletion<'a>(branches: impl Iterator<Item = &'a MergedBranch>) -> Result<()> {
+    let names = branches
+        .map(|branch| branch.name.as_str())
+        .collect::<Vec<_>>();
+    if names.is_empty() || !can_ask_questions() || !console::user_attended_stderr() {
+        return Ok(());
+    }
+    if !Confirm::with_theme(get_color_theme().as_ref())
+        .with_prompt(format!("Delete {}?", names.join(", ")))
+        .default(true)
+        .interact()?
+    {
+        bail!(PargitError::UserAborted);
+    }
+    Ok(())
+}
+
+/// Warns about release and hotfix branches, local or remote, without commits for `max_days` days or more
+fn report_stale_releases(repo: &Repository, max_days: u64) -> Result<()> {
+    let prefixes = [ObjectKind::Release, ObjectKind::Hotfix]
+        .iter()
+        .flat_map(|kind| [format!("{kind}/"), format!("{REMOTE}/{kind}/")])
+        .collect::<Vec<_>>();
+    // a branch is only as stale as the latest of its local and remote copies
+    let mut latest = BTreeMap::new();
+    for branch_type in [BranchType::Local, BranchType::Remote] {
+        for (name, time) in repo.branch_commit_times(branch_type, &prefixes)? {
+            let name = name
+                .strip_prefix(&format!("{REMOTE}/"))
+                .map(String::from)
+                .unwrap_or(name);
+            let entry = latest.entry(name).or_insert(time);
+            *entry = time.max(*entry);
+        }
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let mut stale = Vec::new();
+    for (name, time) in latest {
+        let days = ((now - time) / SECONDS_PER_DAY).max(0) as u64;
+        if days >= max_days {
+            warn!(
+                "{name} was left unfinished, without commits for {days} days. Finish or delete it"
+            );
+            stale.push(StaleBranchReport { name, days });
+        }
+    }
+    output::record(|report| report.stale_branches = Some(stale));
+    Ok(())
+}
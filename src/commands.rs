@@ -1,13 +1,13 @@
 use anyhow::Error;
 use clap::{Parser, Subcommand};
 use semver::Version;
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 use strum_macros::EnumString;
 
 #[derive(Subcommand)]
 pub enum Command {
-    /// Creates a skeleton .pargit.toml configuration in the current repository
-    Configure,
+    /// Sets up pargit in the repository, creating the develop branch and a .pargit.toml configuration
+    Configure(ConfigureOptions),
     #[clap(subcommand)]
     /// Manipulate hotfix releases
     Hotfix(ReleaseCommand),
@@ -23,8 +23,87 @@ pub enum Command {
     #[clap(subcommand)]
     /// Manipulates versions of the current repository
     Version(VersionCommand),
-    /// Cleans up the current branch if it is already merged to develop or main branches.
-    Cleanup,
+    /// Deletes merged branches, prunes stale remote-tracking branches and reports unfinished releases
+    Cleanup(CleanupOptions),
+    /// Checks the repository and configuration for common problems, suggesting how to fix them
+    Doctor,
+    #[clap(subcommand)]
+    /// Inspects and maintains the configuration
+    Config(ConfigCommand),
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Prints the effective configuration, merged from all configuration sources
+    Show {
+        #[clap(long)]
+        /// also prints where each value was set
+        origin: bool,
+    },
+    /// Rewrites deprecated settings in a configuration file, keeping its formatting and comments
+    Migrate {
+        /// the configuration file to migrate. Defaults to the repository's .pargit.toml
+        file: Option<PathBuf>,
+    },
+}
+
+impl Command {
+    /// Whether the command may switch branches, commit or modify files, and therefore requires a clean working tree
+    pub fn modifies_work_tree(&self) -> bool {
+        match self {
+            Command::Configure(_) | Command::Config(_) | Command::Doctor => false,
+            Command::Hotfix(cmd) | Command::Release(cmd) => !matches!(
+                cmd,
+                ReleaseCommand::Publish { .. } | ReleaseCommand::Info { .. }
+            ),
+            Command::Feature(cmd) | Command::Bugfix(cmd) => {
+                !matches!(cmd, FlowCommand::Publish { .. })
+            }
+            Command::Version(_) | Command::Cleanup(_) => true,
+        }
+    }
+}
+
+#[derive(Parser)]
+pub struct ConfigureOptions {
+    #[clap(long)]
+    /// uses detected values and the given flags instead of asking questions
+    pub non_interactive: bool,
+
+    #[clap(long)]
+    /// name of the main branch (detected from origin/HEAD by default)
+    pub main_branch: Option<String>,
+
+    #[clap(long)]
+    /// name of the develop branch
+    pub develop_branch: Option<String>,
+
+    #[clap(long)]
+    /// prefix for release tags (suggested from existing tags by default)
+    pub tag_prefix: Option<String>,
+
+    #[clap(long)]
+    /// creates and pushes the develop branch if it doesn't exist, without asking
+    pub create_develop: bool,
+
+    #[clap(long)]
+    /// overwrites an existing .pargit.toml
+    pub force: bool,
+
+    #[clap(long)]
+    /// writes a commented sample configuration listing all settings instead
+    pub sample: bool,
+}
+
+#[derive(Parser)]
+pub struct CleanupOptions {
+    #[clap(long)]
+    /// only lists the branches that would be deleted
+    pub dry_run: bool,
+
+    #[clap(long)]
+    /// also deletes merged feature and bugfix branches from the remote
+    pub remote: bool,
 }
 
 #[derive(Parser)]
@@ -32,6 +111,25 @@ pub struct ReleaseOptions {
     #[clap(long)]
     /// avoids pulling upstream when performing the release
     pub no_pull: bool,
+
+    #[clap(long)]
+    /// releases even if the version is inconsistent with existing tags
+    pub force: bool,
+}
+
+#[derive(Parser)]
+pub struct VersionFileSelection {
+    #[clap(long, conflicts_with_all = ["version_file", "all_version_files"])]
+    /// bumps only the version file belonging to the specified package
+    pub package: Option<String>,
+
+    #[clap(long = "version-file", conflicts_with = "all_version_files")]
+    /// bumps only the specified version file (relative to the project path)
+    pub version_file: Option<PathBuf>,
+
+    #[clap(long)]
+    /// bumps all version files found in the project, even if their versions differ
+    pub all_version_files: bool,
 }
 
 #[derive(Subcommand)]
@@ -44,6 +142,11 @@ pub enum ReleaseCommand {
         #[clap(long = "from-ref")]
         /// Starts the release branch from the specified ref (commit hash, branch name, etc.)
         from_ref: Option<String>,
+        #[clap(flatten)]
+        selection: VersionFileSelection,
+        #[clap(long)]
+        /// Starts the release even if its version is inconsistent with existing tags
+        force: bool,
     },
     /// Publishes this release to a remote branch upstream
     Publish {
@@ -63,11 +166,34 @@ pub enum ReleaseCommand {
         /// Name of the release branch to finalize. Defaults to current branch
         name: Option<String>,
     },
+    /// Completes a release finished with protected_main_branch, once its branch has been merged into the main branch.
+    /// This tags the release and merges it back to the develop branch
+    Complete {
+        /// Name of the release to complete. Defaults to the current branch
+        name: Option<String>,
+    },
+    /// Withdraws a broken release. This replaces its tag with a yanked/ tag, locally and on the remote, so that its
+    /// version is neither considered the latest release nor reused
+    Yank {
+        /// Version (or tag) of the release to yank
+        version: String,
+        #[clap(long)]
+        /// Also reverts the changes the release brought into the main and develop branches, and pushes them
+        revert: bool,
+    },
+    /// Prints who released a version, when, from which machine, with which pargit version and which checks passed
+    Info {
+        /// Version (or tag) of the release
+        version: String,
+    },
     /// Releases a version in one shot. This means creating the branch, bumping its version as specified, and finalizing a release from it
     Version {
         #[clap(flatten)]
         options: ReleaseOptions,
 
+        #[clap(flatten)]
+        selection: VersionFileSelection,
+
         /// Kind of release to perform (major, minor or patch)
         spec: VersionSpec,
     },
@@ -79,6 +205,9 @@ pub enum FlowCommand {
         name: String,
         #[clap(long = "from-ref")]
         from_ref: Option<String>,
+        #[clap(long)]
+        /// turns the name into a valid branch name, e.g. "Add login page" into add-login-page
+        slugify: bool,
     },
     Publish {
         name: Option<String>,
@@ -93,7 +222,11 @@ pub enum FlowCommand {
 
 #[derive(Subcommand)]
 pub enum VersionCommand {
-    Bump { kind: BumpKind },
+    Bump {
+        kind: BumpKind,
+        #[clap(flatten)]
+        selection: VersionFileSelection,
+    },
 }
 
 #[derive(Clone, Copy, EnumString, Debug)]
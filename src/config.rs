@@ -1,10 +1,32 @@
-use anyhow::Result;
-use std::path::{Path, PathBuf};
+use crate::{error::PargitError, repo::Repository};
+use anyhow::{bail, Context, Result};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use toml::{value::Table, Value};
 
-const CONFIG_FILENAME: &str = ".pargit.toml";
+pub const CONFIG_FILENAME: &str = ".pargit.toml";
+const LOCAL_CONFIG_FILENAME: &str = "pargit.toml";
+const ENV_PREFIX: &str = "PARGIT_";
+
+/// The configuration format version written by `pargit config migrate`
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Settings superseded by newer ones, which `pargit config migrate` rewrites
+const DEPRECATED_KEYS: &[(&str, &str)] = &[
+    ("project_subpath", "project.subpath"),
+    ("master_branch_name", "main_branch_name"),
+];
 
 #[derive(serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
+    /// Version of the configuration format. Files without one predate versioning
+    #[serde(default)]
+    pub schema_version: Option<u32>,
+
+    /// Deprecated in favor of `project.subpath`
     #[serde(default)]
     pub project_subpath: Option<PathBuf>,
 
@@ -17,16 +39,332 @@ pub struct Config {
     #[serde(default = "default_develop_branch")]
     pub develop_branch_name: String,
 
+    /// Creates signed release tags, according to the `user.signingkey` and `gpg.format` git configuration
+    #[serde(default)]
+    pub sign_tags: bool,
+
+    /// Signs the commits and merges pargit creates
+    #[serde(default)]
+    pub sign_commits: bool,
+
+    /// Finishes releases by pushing a branch to be merged into the main branch (e.g. through a pull request) instead of
+    /// pushing the main branch directly
+    #[serde(default)]
+    pub protected_main_branch: bool,
+
+    /// Stashes local changes before operations requiring a clean working tree, and restores them afterwards
+    #[serde(default)]
+    pub autostash: bool,
+
+    /// Regular expression feature names must match, e.g. "^PROJ-[0-9]+-" to require an issue key
+    #[serde(default)]
+    pub feature_name_pattern: Option<String>,
+
+    /// Number of days without commits after which `pargit cleanup` reports unfinished releases and hotfixes
+    #[serde(default = "default_stale_branch_days")]
+    pub stale_branch_days: u64,
+
+    /// Floating tags (e.g. v1, v1.4) moved to each new release
+    #[serde(default)]
+    pub alias_tags: Vec<AliasTag>,
+
+    /// Creates releases on a code forge (GitHub, Gitea, GitLab) after pushing release tags
+    #[serde(default)]
+    pub forge: Option<ForgeConfig>,
+
+    /// HTTP endpoints notified when branches are finished or deleted, and releases yanked
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+
     #[serde(default)]
     #[serde(rename = "project")]
     pub project_config: ProjectConfig,
+
+    #[serde(default)]
+    pub projects: Vec<NamedProjectConfig>,
+
+    #[serde(default)]
+    pub messages: MessagesConfig,
+
+    /// Where each (dotted) configuration key was set, for keys not set to their default
+    #[serde(skip)]
+    origins: BTreeMap<String, ConfigOrigin>,
+
+    /// Name of the project selected out of `projects`, if any
+    #[serde(skip)]
+    pub project_name: Option<String>,
 }
 
-#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct ProjectConfig {
     pub subpath: Option<PathBuf>,
 
+    #[serde(default, rename = "type")]
+    pub kind: Option<ProjectKind>,
+
+    pub compilation_command: Option<String>,
+
+    #[serde(default)]
+    pub discovery: DiscoveryMode,
+
+    #[serde(default)]
+    pub lock_refresh: LockRefresh,
+}
+
+/// Templates for the commit and tag messages pargit creates. Templates may contain the placeholders `{version}`,
+/// `{previous_version}`, `{kind}`, `{name}`, `{branch}`, `{tag}` and `{commit_count}`
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MessagesConfig {
+    #[serde(default = "default_bump_commit_message")]
+    pub bump_commit: String,
+
+    #[serde(default = "default_release_merge_message")]
+    pub release_merge: String,
+
+    #[serde(default = "default_develop_merge_message")]
+    pub develop_merge: String,
+
+    #[serde(default = "default_feature_merge_message")]
+    pub feature_merge: String,
+
+    #[serde(default = "default_tag_message")]
+    pub tag: String,
+}
+
+impl Default for MessagesConfig {
+    fn default() -> Self {
+        toml::from_str("").unwrap()
+    }
+}
+
+fn default_bump_commit_message() -> String {
+    "pargit: Bump version".into()
+}
+fn default_release_merge_message() -> String {
+    "Merge {kind} branch {name}".into()
+}
+fn default_develop_merge_message() -> String {
+    "Merge {branch} branch".into()
+}
+fn default_feature_merge_message() -> String {
+    "Merge {branch}".into()
+}
+fn default_tag_message() -> String {
+    "{tag}".into()
+}
+
+/// Renders a message template, replacing `{placeholder}`s with their values. `{{` and `}}` stand for literal braces
+pub fn render_template(template: &str, values: &[(&str, &str)]) -> Result<String> {
+    let mut returned = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        returned.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if let Some(stripped) = rest.strip_prefix("{{").or_else(|| rest.strip_prefix("}}")) {
+            returned.push_str(&rest[..1]);
+            rest = stripped;
+            continue;
+        }
+        let end = match rest.find('}') {
+            Some(end) if rest.starts_with('{') => end,
+            _ => bail!(PargitError::ConfigInvalid(format!(
+                "Unbalanced braces in message template {template:?}"
+            ))),
+        };
+        let placeholder = &rest[1..end];
+        let value = values
+            .iter()
+            .find(|(key, _)| *key == placeholder)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| {
+                PargitError::ConfigInvalid(format!(
+                    "Unknown placeholder {{{placeholder}}} in message template {template:?}"
+                ))
+            })?;
+        returned.push_str(value);
+        rest = &rest[end + 1..];
+    }
+    returned.push_str(rest);
+    Ok(returned)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AliasTag {
+    /// Tracks the latest release of a major version, e.g. v1
+    Major,
+    /// Tracks the latest release of a minor version, e.g. v1.4
+    Minor,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForgeConfig {
+    pub provider: ForgeProvider,
+
+    /// Base URL of the forge's API. Defaults to the public instance of the provider, if there is one
+    pub base_url: Option<String>,
+
+    /// The repository on the forge, e.g. "owner/name"
+    pub repository: String,
+
+    /// Environment variable holding the API token
+    #[serde(default = "default_forge_token_env")]
+    pub token_env: String,
+
+    /// Changelog file (relative to the project) from which release descriptions are taken
+    #[serde(default = "default_changelog")]
+    pub changelog: PathBuf,
+
+    /// Glob patterns (relative to the project) of files to attach to releases
+    #[serde(default)]
+    pub assets: Vec<String>,
+}
+
+fn default_forge_token_env() -> String {
+    "PARGIT_FORGE_TOKEN".into()
+}
+fn default_changelog() -> PathBuf {
+    "CHANGELOG.md".into()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForgeProvider {
+    Github,
+    Gitea,
+    Gitlab,
+}
+
+impl ForgeProvider {
+    pub fn default_base_url(&self) -> Option<&'static str> {
+        match self {
+            ForgeProvider::Github => Some("https://api.github.com"),
+            ForgeProvider::Gitlab => Some("https://gitlab.com"),
+            ForgeProvider::Gitea => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ForgeProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ForgeProvider::Github => "GitHub",
+            ForgeProvider::Gitea => "Gitea",
+            ForgeProvider::Gitlab => "GitLab",
+        })
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub url: String,
+
+    /// Events sent to this webhook, either an action ("finish", "delete", "yank") or an action of a specific kind of branch
+    /// (e.g. "release_finish"). All events are sent when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Number of additional delivery attempts after a failure
+    #[serde(default = "default_webhook_retries")]
+    pub retries: u32,
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+fn default_webhook_retries() -> u32 {
+    2
+}
+
+impl WebhookConfig {
+    pub fn accepts(&self, kind: &str, action: &str) -> bool {
+        self.events.is_empty()
+            || self
+                .events
+                .iter()
+                .any(|event| event == action || *event == format!("{kind}_{action}"))
+    }
+}
+
+/// A single project out of several residing in the same repository
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamedProjectConfig {
+    pub name: String,
+
+    /// Prefix for this project's tags, following the project name (e.g. "v" for sdk-v1.2.0)
+    pub tag_prefix: Option<String>,
+
+    // the settings of ProjectConfig, spelled out since serde can't deny unknown fields of flattened structs
+    pub subpath: Option<PathBuf>,
+
+    #[serde(default, rename = "type")]
+    pub kind: Option<ProjectKind>,
+
     pub compilation_command: Option<String>,
+
+    #[serde(default)]
+    pub discovery: DiscoveryMode,
+
+    #[serde(default)]
+    pub lock_refresh: LockRefresh,
+}
+
+impl NamedProjectConfig {
+    pub fn project_config(&self) -> ProjectConfig {
+        ProjectConfig {
+            subpath: self.subpath.clone(),
+            kind: self.kind,
+            compilation_command: self.compilation_command.clone(),
+            discovery: self.discovery,
+            lock_refresh: self.lock_refresh,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectKind {
+    Rust,
+    /// A project without version files, released by tags alone
+    Generic,
+}
+
+/// Controls how pargit updates lock files (e.g. Cargo.lock) after bumping versions
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockRefresh {
+    /// Runs the project's compilation command
+    #[default]
+    Compile,
+    /// Rewrites the versions of the bumped packages in the lock file directly
+    Rewrite,
+    /// Runs `cargo update --workspace --offline`
+    CargoUpdate,
+}
+
+/// Controls how pargit locates the version files (e.g. Cargo.toml) of the project
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryMode {
+    /// Walks the repository directory tree, skipping ignored files, submodules and build directories
+    #[default]
+    Walk,
+    /// Considers only files tracked in the git index
+    Index,
+    /// Considers only the root manifest and its workspace members
+    Workspace,
+}
+
+fn default_stale_branch_days() -> u64 {
+    30
 }
 
 fn default_main_branch() -> String {
@@ -42,14 +380,156 @@ impl Default for Config {
     }
 }
 
+/// Where an effective configuration value was set
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigOrigin {
+    Default,
+    File(PathBuf),
+    Environment(String),
+    CommandLine,
+    /// Detected from the repository, e.g. from "origin/HEAD"
+    Detected(String),
+}
+
+impl std::fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigOrigin::Default => f.write_str("default"),
+            ConfigOrigin::File(path) => write!(f, "{}", path.display()),
+            ConfigOrigin::Environment(name) => write!(f, "environment variable {name}"),
+            ConfigOrigin::CommandLine => f.write_str("command line"),
+            ConfigOrigin::Detected(source) => write!(f, "detected from {source}"),
+        }
+    }
+}
+
 impl Config {
-    pub fn load(project_root: &Path) -> Result<Self> {
-        let path = project_root.join(CONFIG_FILENAME);
+    /// Loads the configuration of the repository at `repo_root`, layering (from lowest to highest precedence) the
+    /// user's configuration file, the repository's .pargit.toml, the untracked .git/pargit.toml, `PARGIT_*`
+    /// environment variables and the given command line overrides on top of the defaults
+    pub fn load(repo_root: &Path, overrides: Table) -> Result<Self> {
+        let repo_root = &repo_root
+            .canonicalize()
+            .unwrap_or_else(|_| repo_root.to_owned());
+        let defaults = match Value::try_from(Self::default())? {
+            Value::Table(table) => table,
+            _ => unreachable!(),
+        };
+        let mut layered = Table::new();
+        let mut origins = BTreeMap::new();
+        let mut apply = |table: Table, origin: ConfigOrigin| {
+            let table = normalize_aliases(table);
+            for (key, _) in flatten(&table) {
+                origins.insert(key, origin.clone());
+            }
+            merge(&mut layered, table);
+        };
 
-        if path.exists() {
-            Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
-        } else {
-            Ok(Self::default())
+        let local_config_path = git2::Repository::open(repo_root)
+            .map(|repo| repo.path().to_owned())
+            .unwrap_or_else(|_| repo_root.join(".git"))
+            .join(LOCAL_CONFIG_FILENAME);
+        for path in user_config_path()
+            .into_iter()
+            .chain([repo_root.join(CONFIG_FILENAME), local_config_path])
+        {
+            if let Some(table) = read_config_file(&path)? {
+                log::debug!("Loaded configuration from {path:?}");
+                apply(table, ConfigOrigin::File(path));
+            }
+        }
+
+        for (key, default) in &defaults {
+            // only scalar settings can be set through the environment. This also leaves out PARGIT_PROJECT, which
+            // selects a project rather than configuring one
+            if !matches!(
+                default,
+                Value::String(_) | Value::Boolean(_) | Value::Integer(_)
+            ) {
+                continue;
+            }
+            let name = format!("{ENV_PREFIX}{}", key.to_uppercase());
+            if let Ok(raw) = std::env::var(&name) {
+                let value = parse_env_value(&raw, default).ok_or_else(|| {
+                    PargitError::ConfigInvalid(format!("Invalid value {raw:?} for {name}"))
+                })?;
+                apply(
+                    std::iter::once((key.clone(), value)).collect(),
+                    ConfigOrigin::Environment(name),
+                );
+            }
+        }
+
+        apply(overrides, ConfigOrigin::CommandLine);
+
+        let mut returned: Self = Value::Table(layered)
+            .try_into()
+            .context(PargitError::ConfigInvalid("Invalid configuration".into()))?;
+        if let Ok(repo) = Repository::on_path(repo_root) {
+            returned.resolve_main_branch(&repo, &mut origins);
+        }
+        returned.origins = origins;
+        Ok(returned)
+    }
+
+    /// Detects the main branch when it isn't configured, and warns when the configured one isn't the remote's default
+    /// (unless that's the develop branch)
+    fn resolve_main_branch(
+        &mut self,
+        repo: &Repository,
+        origins: &mut BTreeMap<String, ConfigOrigin>,
+    ) {
+        if origins.contains_key("main_branch_name") {
+            if let Some(remote_head) = repo.remote_head_branch("origin") {
+                if remote_head != self.main_branch_name && remote_head != self.develop_branch_name {
+                    log::warn!(
+                        "main_branch_name is configured as {}, but the default branch of origin is {remote_head}",
+                        self.main_branch_name
+                    );
+                }
+            }
+            return;
+        }
+        match repo.detect_main_branch("origin", &self.develop_branch_name) {
+            Some((name, source)) => {
+                log::info!(
+                    "main_branch_name is not configured. Using {name} (detected from {source})"
+                );
+                self.main_branch_name = name;
+                origins.insert("main_branch_name".into(), ConfigOrigin::Detected(source));
+            }
+            None => log::info!(
+                "main_branch_name is not configured and could not be detected. Using {}",
+                self.main_branch_name
+            ),
+        }
+    }
+
+    /// The effective configuration values, keyed by their dotted names, along with where they were set
+    pub fn effective_values(&self) -> Result<Vec<(String, Value, ConfigOrigin)>> {
+        let table = match Value::try_from(self)? {
+            Value::Table(table) => table,
+            _ => unreachable!(),
+        };
+        Ok(flatten(&table)
+            .into_iter()
+            .map(|(key, value)| {
+                let origin = self.origin(&key);
+                (key, value.clone(), origin)
+            })
+            .collect())
+    }
+
+    pub fn origin(&self, key: &str) -> ConfigOrigin {
+        let mut key = key;
+        loop {
+            if let Some(origin) = self.origins.get(key) {
+                return origin.clone();
+            }
+            match key.rsplit_once('.') {
+                Some((parent, _)) => key = parent,
+                None => return ConfigOrigin::Default,
+            }
         }
     }
 
@@ -57,16 +537,264 @@ impl Config {
         include_str!("../sample-config.toml")
     }
 
+    /// Applies the settings of the project named `name` out of `projects`. When no name is given, a single configured
+    /// project is selected implicitly
+    pub fn select_project(&mut self, name: Option<&str>) -> Result<()> {
+        let names = || {
+            self.projects
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let project = match name {
+            None if self.projects.is_empty() => return Ok(()),
+            None if self.projects.len() > 1 => {
+                bail!(PargitError::ConfigInvalid(format!(
+                    "Multiple projects configured ({}). Select one with --project",
+                    names()
+                )))
+            }
+            None => &self.projects[0],
+            Some(name) => self
+                .projects
+                .iter()
+                .find(|p| p.name == name)
+                .ok_or_else(|| {
+                    PargitError::ConfigInvalid(format!(
+                        "Unknown project {name:?}. Configured projects: {}",
+                        names()
+                    ))
+                })?,
+        };
+
+        log::debug!("Selected project {:?}", project.name);
+        self.tag_prefix = format!(
+            "{}-{}",
+            project.name,
+            project.tag_prefix.as_deref().unwrap_or(&self.tag_prefix)
+        );
+        self.project_config = project.project_config();
+        self.project_name = Some(project.name.clone());
+        Ok(())
+    }
+
+    /// Returns the prefix of release names (and tags) of the selected project, e.g. "sdk-"
+    pub fn release_namespace(&self) -> String {
+        self.project_name
+            .as_ref()
+            .map(|name| format!("{name}-"))
+            .unwrap_or_default()
+    }
+
+    pub fn get_release_name(&self, version: &str) -> String {
+        format!("{}{}", self.release_namespace(), version)
+    }
+
+    /// Returns the version part of a (possibly namespaced) release name
+    pub fn release_name_version<'a>(&self, release_name: &'a str) -> &'a str {
+        release_name
+            .strip_prefix(&self.release_namespace())
+            .unwrap_or(release_name)
+    }
+
     pub fn get_tag_name(&self, version: &str, prefix: Option<String>) -> String {
         let prefix = prefix.as_deref().unwrap_or(&self.tag_prefix);
         format!("{}{}", prefix, version)
     }
 }
 
+fn user_config_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("pargit").join("config.toml"))
+}
+
+fn read_config_file(path: &Path) -> Result<Option<Table>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let invalid = || PargitError::ConfigInvalid(format!("Invalid configuration file {path:?}"));
+    let contents = std::fs::read_to_string(path)?;
+    // deserializing each file on its own reports errors in the file they appear in
+    let config = toml::from_str::<Config>(&contents)
+        .map_err(with_suggestion)
+        .with_context(invalid)?;
+    if let Some(version) = config.schema_version.filter(|v| *v > SCHEMA_VERSION) {
+        bail!(PargitError::ConfigInvalid(format!(
+            "{path:?} uses configuration schema version {version}, but this version of pargit supports up to version {SCHEMA_VERSION}. Please upgrade pargit"
+        )));
+    }
+    let table: Table = toml::from_str(&contents).with_context(invalid)?;
+    let deprecated = DEPRECATED_KEYS
+        .iter()
+        .filter(|(key, _)| table.contains_key(*key))
+        .map(|(key, replacement)| format!("{key} (use {replacement} instead)"))
+        .collect::<Vec<_>>();
+    if !deprecated.is_empty() {
+        log::warn!(
+            "{path:?} uses deprecated settings: {}. Run `pargit config migrate` to update it",
+            deprecated.join(", ")
+        );
+    }
+    Ok(Some(table))
+}
+
+/// Adds a suggestion of the closest known key to errors about unknown keys, e.g. when misspelling a setting
+fn with_suggestion(error: toml::de::Error) -> anyhow::Error {
+    let message = error.to_string();
+    match suggest_key(&message) {
+        Some(suggestion) => anyhow::format_err!("{message}. Did you mean `{suggestion}`?"),
+        None => error.into(),
+    }
+}
+
+/// Finds the expected key most similar to the unknown one in serde's "unknown field `x`, expected one of `a`, `b`"
+/// errors
+fn suggest_key(message: &str) -> Option<&str> {
+    let (unknown, expected) = message
+        .strip_prefix("unknown field `")?
+        .split_once("`, expected ")?;
+    let expected = expected.split(" for key ").next()?;
+    expected
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .map(|candidate| (strsim::jaro_winkler(unknown, candidate), candidate))
+        .filter(|(similarity, _)| *similarity > 0.8)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate)
+}
+
+/// Rewrites the deprecated settings of the configuration file at `path` to their replacements and marks it with the
+/// current schema version, keeping its formatting and comments. Returns whether the file changed
+pub fn migrate_file(path: &Path) -> Result<bool> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed reading {path:?}"))?;
+    let mut document: toml_edit::DocumentMut = contents.parse().with_context(|| {
+        PargitError::ConfigInvalid(format!("Invalid configuration file {path:?}"))
+    })?;
+    migrate_document(&mut document)?;
+    let migrated = document.to_string();
+    if migrated == contents {
+        return Ok(false);
+    }
+    std::fs::write(path, migrated).with_context(|| format!("Failed writing {path:?}"))?;
+    Ok(true)
+}
+
+fn migrate_document(document: &mut toml_edit::DocumentMut) -> Result<()> {
+    let root = document.as_table_mut();
+
+    if let Some((key, item)) = root.remove_entry("project_subpath") {
+        let project = root
+            .entry("project")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| PargitError::ConfigInvalid("project must be a table".into()))?;
+        if project.contains_key("subpath") {
+            log::warn!("Dropping project_subpath, as project.subpath is already set");
+        } else {
+            project.insert("subpath", item);
+            if let Some(mut subpath_key) = project.key_mut("subpath") {
+                *subpath_key.leaf_decor_mut() = key.leaf_decor().clone();
+            }
+        }
+    }
+
+    if root.contains_key("master_branch_name") {
+        if root.contains_key("main_branch_name") {
+            log::warn!("Dropping master_branch_name, as main_branch_name is already set");
+            root.remove("master_branch_name");
+        } else {
+            // reinserting all entries keeps the renamed one in its place
+            let names = root
+                .iter()
+                .map(|(name, _)| name.to_owned())
+                .collect::<Vec<_>>();
+            for name in names {
+                let (mut key, item) = root.remove_entry(&name).unwrap();
+                if name == "master_branch_name" {
+                    key = toml_edit::Key::new("main_branch_name")
+                        .with_leaf_decor(key.leaf_decor().clone())
+                        .with_dotted_decor(key.dotted_decor().clone());
+                }
+                root.insert_formatted(&key, item);
+            }
+        }
+    }
+
+    let version = root
+        .get("schema_version")
+        .and_then(|item| item.as_integer())
+        .unwrap_or(1);
+    if version < i64::from(SCHEMA_VERSION) {
+        root.insert(
+            "schema_version",
+            toml_edit::value(i64::from(SCHEMA_VERSION)),
+        );
+    }
+    Ok(())
+}
+
+fn normalize_aliases(mut table: Table) -> Table {
+    if let Some(value) = table.remove("master_branch_name") {
+        table.entry("main_branch_name").or_insert(value);
+    }
+    table
+}
+
+/// Merges `overlay` into `base`, recursing into tables and replacing any other values
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Lists the non-table values nested in `table` by their dotted keys
+fn flatten(table: &Table) -> Vec<(String, &Value)> {
+    let mut returned = Vec::new();
+    for (key, value) in table {
+        match value {
+            Value::Table(nested) => returned.extend(
+                flatten(nested)
+                    .into_iter()
+                    .map(|(nested_key, value)| (format!("{key}.{nested_key}"), value)),
+            ),
+            _ => returned.push((key.clone(), value)),
+        }
+    }
+    returned
+}
+
+/// Parses a scalar value given through the environment, according to the type of its default
+fn parse_env_value(raw: &str, default: &Value) -> Option<Value> {
+    Some(match default {
+        Value::String(_) => Value::String(raw.to_owned()),
+        Value::Boolean(_) => Value::Boolean(match raw.to_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" | "" => false,
+            _ => return None,
+        }),
+        Value::Integer(_) => Value::Integer(raw.parse().ok()?),
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
 
-    use super::Config;
+    use super::{
+        flatten, merge, migrate_document, normalize_aliases, render_template, with_suggestion,
+        Config, ProjectKind,
+    };
     use itertools::Itertools;
 
     #[test]
@@ -80,4 +808,132 @@ mod tests {
 
         let _config: Config = toml::from_str(&lines).unwrap();
     }
+
+    #[test]
+    fn test_select_project_namespaces_tags_and_releases() {
+        let load = || -> Config {
+            toml::from_str(
+                r#"
+            tag_prefix = "v"
+
+            [[projects]]
+            name = "cli"
+            subpath = "cli"
+
+            [[projects]]
+            name = "sdk"
+            subpath = "sdk"
+            type = "generic"
+            tag_prefix = ""
+            "#,
+            )
+            .unwrap()
+        };
+
+        assert!(load().select_project(None).is_err());
+        assert!(load().select_project(Some("nope")).is_err());
+
+        let mut config = load();
+        config.select_project(Some("cli")).unwrap();
+        assert_eq!(config.get_release_name("1.2.0"), "cli-1.2.0");
+        assert_eq!(config.get_tag_name("1.2.0", None), "cli-v1.2.0");
+        assert_eq!(config.release_name_version("cli-1.2.0"), "1.2.0");
+
+        let mut config = load();
+        config.select_project(Some("sdk")).unwrap();
+        assert_eq!(config.get_tag_name("1.2.0", None), "sdk-1.2.0");
+        assert_eq!(config.project_config.kind, Some(ProjectKind::Generic));
+    }
+
+    #[test]
+    fn test_merge_layers() {
+        let layer = |s: &str| normalize_aliases(toml::from_str(s).unwrap());
+        let mut merged = layer("tag_prefix = \"u\"\nsign_tags = true\n[messages]\ntag = \"x\"");
+        merge(
+            &mut merged,
+            layer("tag_prefix = \"v\"\nmaster_branch_name = \"main\"\n[messages]\nbump_commit = \"y\""),
+        );
+        let flat = flatten(&merged)
+            .into_iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flat,
+            [
+                "main_branch_name=\"main\"",
+                "messages.bump_commit=\"y\"",
+                "messages.tag=\"x\"",
+                "sign_tags=true",
+                "tag_prefix=\"v\""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_rejected_with_suggestion() {
+        let error =
+            |s: &str| with_suggestion(toml::from_str::<Config>(s).err().unwrap()).to_string();
+        assert!(error("develop_branch = \"dev\"").contains("Did you mean `develop_branch_name`?"));
+        assert!(error("[messages]\nbump_comit = \"x\"").contains("Did you mean `bump_commit`?"));
+        assert!(!error("something_else = 1").contains("Did you mean"));
+    }
+
+    #[test]
+    fn test_unknown_project_keys_rejected() {
+        let error = with_suggestion(
+            toml::from_str::<Config>("[[projects]]\nname = \"a\"\nsubpth = \"a\"")
+                .err()
+                .unwrap(),
+        )
+        .to_string();
+        assert!(error.contains("Did you mean `subpath`?"), "{}", error);
+    }
+
+    #[test]
+    fn test_migrate_document() {
+        let mut document: toml_edit::DocumentMut = r#"# Pargit configuration
+tag_prefix = "v"
+# the stable branch
+master_branch_name = "main"
+# where the crate lives
+project_subpath = "crate"
+
+[messages]
+tag = "{tag}"
+"#
+        .parse()
+        .unwrap();
+        migrate_document(&mut document).unwrap();
+        let migrated = document.to_string();
+        assert_eq!(
+            migrated,
+            r#"# Pargit configuration
+tag_prefix = "v"
+# the stable branch
+main_branch_name = "main"
+schema_version = 2
+
+[messages]
+tag = "{tag}"
+
+[project]
+# where the crate lives
+subpath = "crate"
+"#
+        );
+        let config: Config = toml::from_str(&migrated).unwrap();
+        assert_eq!(config.main_branch_name, "main");
+        assert_eq!(config.project_config.subpath, Some("crate".into()));
+    }
+
+    #[test]
+    fn test_render_template() {
+        let values = [("version", "1.2.0"), ("kind", "release")];
+        assert_eq!(
+            render_template("chore({kind}): {version} {{x}}", &values).unwrap(),
+            "chore(release): 1.2.0 {x}"
+        );
+        assert!(render_template("{nope}", &values).is_err());
+        assert!(render_template("{version", &values).is_err());
+    }
 }
@@ -0,0 +1,316 @@
+use crate::{
+    error::PargitError,
+    output::{self, CheckReport},
+    pargit::{agrees_with_tags, Pargit, TEMP_BRANCH_PREFIX},
+    repo::Repository,
+    utils::{shell_quote, PathExt},
+};
+
+use anyhow::{bail, Result};
+use console::style;
+use std::path::Path;
+
+/// What a failed check found, and how to fix it
+struct Problem {
+    description: String,
+    hint: String,
+}
+
+type Outcome = Result<Option<Problem>>;
+
+fn problem(description: impl Into<String>, hint: impl Into<String>) -> Outcome {
+    Ok(Some(Problem {
+        description: description.into(),
+        hint: hint.into(),
+    }))
+}
+
+/// Runs diagnostic checks on the repository and project configuration, printing a pass/fail line for each (unless
+/// `quiet`). Fails if any of the checks failed
+pub fn run(project: &Pargit, quiet: bool) -> Result<()> {
+    let config = project.config();
+    let repo = project.repo();
+    let main = &config.main_branch_name;
+    let develop = &config.develop_branch_name;
+
+    let mut reports = Vec::new();
+    let mut check = |name: String, outcome: Outcome| {
+        let report = match outcome {
+            Ok(None) => CheckReport {
+                name,
+                ok: true,
+                problem: None,
+                hint: None,
+            },
+            Ok(Some(problem)) => CheckReport {
+                name,
+                ok: false,
+                problem: Some(problem.description),
+                hint: Some(problem.hint),
+            },
+            Err(e) => CheckReport {
+                name,
+                ok: false,
+                problem: Some(format!("{e:#}")),
+                hint: None,
+            },
+        };
+        if !quiet {
+            print_report(&report);
+        }
+        reports.push(report);
+    };
+
+    check("HEAD is on a branch".into(), check_head(repo, develop));
+    check("Repository has full history".into(), check_shallow(repo));
+    let mut branches_exist = true;
+    for (branch, key) in [(main, "main_branch_name"), (develop, "develop_branch_name")] {
+        let exists = repo.find_branch(branch).is_ok();
+        branches_exist &= exists;
+        check(
+            format!("{branch} branch exists"),
+            if exists {
+                Ok(None)
+            } else {
+                problem(
+                    format!("{branch} branch not found"),
+                    format!("Run `pargit configure` to create it, or set {key} in .pargit.toml"),
+                )
+            },
+        );
+        if exists {
+            check(
+                format!("{branch} tracks an up to date remote branch"),
+                check_upstream(repo, branch),
+            );
+        }
+    }
+    if branches_exist {
+        check(
+            format!("{main} is merged into {develop}"),
+            check_merged(repo, main, develop),
+        );
+    }
+    check(
+        "No leftover temporary branches".into(),
+        check_temp_branches(repo),
+    );
+    check(
+        "Release tags use the configured tag prefix".into(),
+        check_tag_prefix(project),
+    );
+    check(
+        "Version files match the latest release tag".into(),
+        check_versions(project),
+    );
+    if let Some(compilation_command) = project.compilation_command() {
+        let program = compilation_command
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+        check(
+            format!("Compilation command {program} is available"),
+            check_program(project.project_path(), program),
+        );
+    }
+    check("Git hooks are executable".into(), check_hooks(repo));
+    if config.sign_tags || config.sign_commits {
+        check(
+            "Signing key is available".into(),
+            match project.check_signing() {
+                Ok(()) => Ok(None),
+                Err(e) => problem(
+                    format!("{e:#}"),
+                    "Configure user.signingkey (and gpg.format) in git, or disable sign_tags and sign_commits",
+                ),
+            },
+        );
+    }
+
+    let total = reports.len();
+    let failed = reports.iter().filter(|report| !report.ok).count();
+    output::record(|report| report.checks = Some(reports));
+    if failed > 0 {
+        bail!(PargitError::CheckFailed(format!(
+            "{failed} of {total} checks failed"
+        )));
+    }
+    Ok(())
+}
+
+fn print_report(report: &CheckReport) {
+    if report.ok {
+        println!("{} {}", style("✔").green(), report.name);
+        return;
+    }
+    println!(
+        "{} {}: {}",
+        style("✘").red(),
+        report.name,
+        report.problem.as_deref().unwrap_or_default()
+    );
+    if let Some(hint) = &report.hint {
+        println!("    {}", style(hint).dim());
+    }
+}
+
+fn check_head(repo: &Repository, develop: &str) -> Outcome {
+    if repo.is_head_detached()? {
+        return problem(
+            "HEAD is detached",
+            format!("Check out a branch, e.g. `git switch {develop}`"),
+        );
+    }
+    Ok(None)
+}
+
+fn check_shallow(repo: &Repository) -> Outcome {
+    if repo.is_shallow() {
+        return problem(
+            "The repository is a shallow clone, missing tags and history pargit relies on",
+            "Run `git fetch --unshallow --tags`",
+        );
+    }
+    Ok(None)
+}
+
+fn check_upstream(repo: &Repository, branch: &str) -> Outcome {
+    let upstream = match repo.branch_upstream_name(branch)? {
+        Some(upstream) => upstream,
+        None if repo.has_remote_branch("origin", branch) => {
+            return problem(
+                format!("{branch} does not track origin/{branch}"),
+                format!("Run `git branch --set-upstream-to=origin/{branch} {branch}`"),
+            )
+        }
+        None => {
+            return problem(
+                format!("{branch} does not exist on origin"),
+                format!("Run `git push -u origin {branch}`"),
+            )
+        }
+    };
+    let behind = repo.count_commits_between(branch, &upstream)?;
+    if behind > 0 {
+        return problem(
+            format!("{branch} is {behind} commits behind {upstream}"),
+            format!("Run `git pull` on {branch}"),
+        );
+    }
+    Ok(None)
+}
+
+fn check_merged(repo: &Repository, main: &str, develop: &str) -> Outcome {
+    if !repo.contains(develop, main)? {
+        return problem(
+            format!("{main} has commits missing from {develop}"),
+            format!("Merge {main} into {develop}, e.g. `git switch {develop} && git merge {main}`"),
+        );
+    }
+    Ok(None)
+}
+
+fn check_temp_branches(repo: &Repository) -> Outcome {
+    let leftovers = repo.local_branch_names(TEMP_BRANCH_PREFIX)?;
+    if !leftovers.is_empty() {
+        return problem(
+            format!("Found {}", leftovers.join(", ")),
+            "Complete releases waiting for merging into a protected main branch with `pargit release complete`, or delete abandoned branches",
+        );
+    }
+    Ok(None)
+}
+
+fn check_tag_prefix(project: &Pargit) -> Outcome {
+    let config = project.config();
+    let expected = config.get_tag_name("", None);
+    if let Some(latest) = project.tagged_versions()?.last() {
+        if latest.prefix != expected {
+            let prefix = latest
+                .prefix
+                .strip_prefix(&config.release_namespace())
+                .unwrap_or(&latest.prefix);
+            return problem(
+                format!(
+                    "The latest release tag {} does not start with the configured prefix {expected:?}",
+                    latest.tag
+                ),
+                format!("Set tag_prefix = {prefix:?} in .pargit.toml"),
+            );
+        }
+    }
+    Ok(None)
+}
+
+fn check_versions(project: &Pargit) -> Outcome {
+    let tagged = project.tagged_versions()?;
+    let latest = match tagged.iter().rfind(|t| !t.yanked) {
+        Some(latest) => latest,
+        None => return Ok(None),
+    };
+    for version_file in project.get_all_version_files()? {
+        let version = version_file.version();
+        if !agrees_with_tags(&version, &tagged) {
+            return problem(
+                format!(
+                    "{:?} has version {version}, but the latest release tag is {}",
+                    version_file.path(),
+                    latest.tag
+                ),
+                "Fix the version in the file, or tag the release it belongs to",
+            );
+        }
+    }
+    Ok(None)
+}
+
+fn check_program(project_path: &Path, program: &str) -> Outcome {
+    if project_path
+        .shell(format!("command -v {}", shell_quote(program)))
+        .is_err()
+    {
+        return problem(
+            format!("{program} was not found"),
+            format!("Install {program}, or set project.compilation_command in .pargit.toml"),
+        );
+    }
+    Ok(None)
+}
+
+fn check_hooks(repo: &Repository) -> Outcome {
+    let hooks_path = repo.hooks_path();
+    if !hooks_path.is_dir() {
+        return Ok(None);
+    }
+    let mut not_executable = Vec::new();
+    for entry in std::fs::read_dir(&hooks_path)? {
+        let path = entry?.path();
+        if path.is_file()
+            && path.extension().is_none_or(|e| e != "sample")
+            && !is_executable(&path)?
+        {
+            not_executable.push(path.file_name().unwrap().to_string_lossy().to_string());
+        }
+    }
+    if !not_executable.is_empty() {
+        return problem(
+            format!(
+                "Hooks {} in {hooks_path:?} are not executable, so git ignores them",
+                not_executable.join(", ")
+            ),
+            "Make them executable with `chmod +x`",
+        );
+    }
+    Ok(None)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+    Ok(path.metadata()?.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> Result<bool> {
+    Ok(true)
+}
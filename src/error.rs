@@ -0,0 +1,71 @@
+/// Categories of failures callers may want to handle differently (e.g. retrying after a lost push race, as opposed to
+/// asking a human for help). These are carried through `anyhow` errors, either as the error itself or as context
+#[derive(Debug)]
+pub enum PargitError {
+    DirtyTree,
+    BehindRemote(String),
+    PushRejected(String),
+    TagExists(String),
+    CheckFailed(String),
+    ConfigInvalid(String),
+    UserAborted,
+}
+
+impl std::fmt::Display for PargitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PargitError::DirtyTree => write!(f, "Repository is dirty!"),
+            PargitError::BehindRemote(branch) => write!(f, "Local {branch} branch is behind remote {branch} branch. Update your local {branch} branch before creating a release."),
+            PargitError::PushRejected(what) => write!(f, "Failed pushing {what}"),
+            PargitError::TagExists(message)
+            | PargitError::CheckFailed(message)
+            | PargitError::ConfigInvalid(message) => f.write_str(message),
+            PargitError::UserAborted => write!(f, "Aborted by user"),
+        }
+    }
+}
+
+impl std::error::Error for PargitError {}
+
+impl PargitError {
+    pub fn find(error: &anyhow::Error) -> Option<&Self> {
+        error.downcast_ref()
+    }
+
+    /// Stable identifier of the error's category, reported in JSON output
+    pub fn code(&self) -> &'static str {
+        match self {
+            PargitError::DirtyTree => "dirty_tree",
+            PargitError::BehindRemote(_) => "behind_remote",
+            PargitError::PushRejected(_) => "push_rejected",
+            PargitError::TagExists(_) => "tag_exists",
+            PargitError::CheckFailed(_) => "check_failed",
+            PargitError::ConfigInvalid(_) => "config_invalid",
+            PargitError::UserAborted => "user_aborted",
+        }
+    }
+
+    /// The process exit code for the error's category, as documented in the README
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            PargitError::DirtyTree => 10,
+            PargitError::BehindRemote(_) => 11,
+            PargitError::PushRejected(_) => 12,
+            PargitError::TagExists(_) => 13,
+            PargitError::CheckFailed(_) => 14,
+            PargitError::ConfigInvalid(_) => 15,
+            PargitError::UserAborted => 16,
+        }
+    }
+}
+
+/// Exit code for errors not falling into any specific category
+pub const GENERIC_EXIT_CODE: i32 = 1;
+
+pub fn error_code(error: &anyhow::Error) -> &'static str {
+    PargitError::find(error).map_or("failed", PargitError::code)
+}
+
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    PargitError::find(error).map_or(GENERIC_EXIT_CODE, PargitError::exit_code)
+}
@@ -0,0 +1,351 @@
+use crate::config::{ForgeConfig, ForgeProvider};
+
+use anyhow::{format_err, Context, Result};
+use log::{debug, info, warn};
+use serde_json::{json, Value};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A client for a code forge's REST API, able to publish releases for pushed tags
+pub trait ForgeClient {
+    /// Creates a release for an existing tag, returning a handle used for uploading assets to it
+    fn create_release(&self, tag: &str, body: &str) -> Result<String>;
+
+    fn upload_asset(&self, release: &str, path: &Path) -> Result<()>;
+}
+
+pub fn client(config: &ForgeConfig) -> Result<Box<dyn ForgeClient>> {
+    let token = std::env::var(&config.token_env).with_context(|| {
+        format!(
+            "Forge token environment variable {} is not set",
+            config.token_env
+        )
+    })?;
+    let base_url = config
+        .base_url
+        .clone()
+        .or_else(|| config.provider.default_base_url().map(String::from))
+        .ok_or_else(|| format_err!("base_url must be configured for {} forges", config.provider))?
+        .trim_end_matches('/')
+        .to_owned();
+    let api = Api {
+        agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+        base_url,
+        repository: config.repository.clone(),
+        token,
+    };
+    Ok(match config.provider {
+        ForgeProvider::Github => Box::new(GitHub(api)),
+        ForgeProvider::Gitea => Box::new(Gitea(api)),
+        ForgeProvider::Gitlab => Box::new(GitLab(api)),
+    })
+}
+
+/// Creates a forge release for `tag`, using the changelog section of `version` as its description and uploading the
+/// configured assets
+pub fn publish_release(
+    config: &ForgeConfig,
+    project_path: &Path,
+    tag: &str,
+    version: &str,
+) -> Result<()> {
+    let client = client(config)?;
+
+    let changelog_path = project_path.join(&config.changelog);
+    let body = match std::fs::read_to_string(&changelog_path) {
+        Ok(changelog) => changelog_section(&changelog, version).unwrap_or_else(|| {
+            debug!("No changelog section found for {version} in {changelog_path:?}");
+            String::new()
+        }),
+        Err(e) => {
+            debug!("Could not read changelog {changelog_path:?}: {e}");
+            String::new()
+        }
+    };
+
+    let release = client.create_release(tag, &body)?;
+    for asset in find_assets(project_path, &config.assets)? {
+        info!("Uploading {asset:?}");
+        client
+            .upload_asset(&release, &asset)
+            .with_context(|| format!("Failed uploading {asset:?}"))?;
+    }
+    Ok(())
+}
+
+fn find_assets(project_path: &Path, patterns: &[String]) -> Result<Vec<PathBuf>> {
+    let mut returned = Vec::new();
+    for pattern in patterns {
+        let full_pattern = project_path.join(pattern);
+        let mut found = false;
+        for path in glob::glob(&full_pattern.to_string_lossy())
+            .with_context(|| format!("Invalid asset pattern {pattern:?}"))?
+        {
+            let path = path?;
+            if path.is_file() {
+                found = true;
+                returned.push(path);
+            }
+        }
+        if !found {
+            warn!("No assets found matching {pattern:?}");
+        }
+    }
+    Ok(returned)
+}
+
+/// Extracts the section of a markdown changelog describing `version`, i.e. the lines following the heading mentioning
+/// it, up to the next heading of the same or a higher level
+pub fn changelog_section(changelog: &str, version: &str) -> Option<String> {
+    let heading_level = |line: &str| {
+        let level = line.chars().take_while(|c| *c == '#').count();
+        (level > 0 && line[level..].starts_with(' ')).then_some(level)
+    };
+    let mentions_version = |line: &str| {
+        line.split(|c: char| !(c.is_alphanumeric() || c == '.' || c == '-' || c == '+'))
+            .any(|word| word == version || word.strip_prefix('v') == Some(version))
+    };
+
+    let mut lines = changelog.lines();
+    let level = lines
+        .by_ref()
+        .find_map(|line| heading_level(line).filter(|_| mentions_version(line)))?;
+    let section = lines
+        .take_while(|line| heading_level(line).map(|l| l > level).unwrap_or(true))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(section.trim().to_owned())
+}
+
+struct Api {
+    agent: ureq::Agent,
+    base_url: String,
+    repository: String,
+    token: String,
+}
+
+impl Api {
+    fn send_json(&self, request: ureq::Request, payload: Value) -> Result<Value> {
+        let url = request.url().to_owned();
+        debug!("POST {url}");
+        check_response(request.send_json(payload), &url)?
+            .into_json()
+            .with_context(|| format!("Invalid response from {url}"))
+    }
+
+    fn send_file(&self, request: ureq::Request, path: &Path) -> Result<ureq::Response> {
+        let url = request.url().to_owned();
+        debug!("POST {url}");
+        let contents = std::fs::read(path).with_context(|| format!("Failed reading {path:?}"))?;
+        check_response(
+            request
+                .set("Content-Type", "application/octet-stream")
+                .send_bytes(&contents),
+            &url,
+        )
+    }
+
+    fn send_multipart(&self, request: ureq::Request, field: &str, path: &Path) -> Result<Value> {
+        let url = request.url().to_owned();
+        debug!("POST {url}");
+        let contents = std::fs::read(path).with_context(|| format!("Failed reading {path:?}"))?;
+        let boundary = "pargit-multipart-boundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+            file_name(path)
+        )
+        .into_bytes();
+        body.extend_from_slice(&contents);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        check_response(
+            request
+                .set(
+                    "Content-Type",
+                    &format!("multipart/form-data; boundary={boundary}"),
+                )
+                .send_bytes(&body),
+            &url,
+        )?
+        .into_json()
+        .with_context(|| format!("Invalid response from {url}"))
+    }
+}
+
+fn check_response(
+    response: std::result::Result<ureq::Response, ureq::Error>,
+    url: &str,
+) -> Result<ureq::Response> {
+    match response {
+        Ok(response) => Ok(response),
+        Err(ureq::Error::Status(status, response)) => Err(format_err!(
+            "Request to {url} failed with status {status}: {}",
+            response.into_string().unwrap_or_default()
+        )),
+        Err(e) => Err(e).with_context(|| format!("Request to {url} failed")),
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Percent-encodes a URL path segment or query value
+fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+struct GitHub(Api);
+
+impl ForgeClient for GitHub {
+    fn create_release(&self, tag: &str, body: &str) -> Result<String> {
+        let api = &self.0;
+        let request = api
+            .agent
+            .post(&format!(
+                "{}/repos/{}/releases",
+                api.base_url, api.repository
+            ))
+            .set("Authorization", &format!("Bearer {}", api.token))
+            .set("Accept", "application/vnd.github+json");
+        let response =
+            api.send_json(request, json!({"tag_name": tag, "name": tag, "body": body}))?;
+        let upload_url = response["upload_url"]
+            .as_str()
+            .ok_or_else(|| format_err!("Release response is missing upload_url"))?;
+        // upload URLs are URI templates, e.g. https://uploads.github.com/repos/o/r/releases/1/assets{?name,label}
+        Ok(upload_url
+            .split_once('{')
+            .map(|(url, _)| url)
+            .unwrap_or(upload_url)
+            .to_owned())
+    }
+
+    fn upload_asset(&self, release: &str, path: &Path) -> Result<()> {
+        let api = &self.0;
+        let request = api
+            .agent
+            .post(&format!("{release}?name={}", encode(&file_name(path))))
+            .set("Authorization", &format!("Bearer {}", api.token))
+            .set("Accept", "application/vnd.github+json");
+        api.send_file(request, path).map(drop)
+    }
+}
+
+struct Gitea(Api);
+
+impl ForgeClient for Gitea {
+    fn create_release(&self, tag: &str, body: &str) -> Result<String> {
+        let api = &self.0;
+        let request = api
+            .agent
+            .post(&format!(
+                "{}/api/v1/repos/{}/releases",
+                api.base_url, api.repository
+            ))
+            .set("Authorization", &format!("token {}", api.token));
+        let response =
+            api.send_json(request, json!({"tag_name": tag, "name": tag, "body": body}))?;
+        response["id"]
+            .as_u64()
+            .map(|id| id.to_string())
+            .ok_or_else(|| format_err!("Release response is missing id"))
+    }
+
+    fn upload_asset(&self, release: &str, path: &Path) -> Result<()> {
+        let api = &self.0;
+        let request = api
+            .agent
+            .post(&format!(
+                "{}/api/v1/repos/{}/releases/{release}/assets?name={}",
+                api.base_url,
+                api.repository,
+                encode(&file_name(path))
+            ))
+            .set("Authorization", &format!("token {}", api.token));
+        api.send_multipart(request, "attachment", path).map(drop)
+    }
+}
+
+struct GitLab(Api);
+
+impl GitLab {
+    fn project_url(&self) -> String {
+        format!(
+            "{}/api/v4/projects/{}",
+            self.0.base_url,
+            encode(&self.0.repository)
+        )
+    }
+}
+
+impl ForgeClient for GitLab {
+    fn create_release(&self, tag: &str, body: &str) -> Result<String> {
+        let api = &self.0;
+        let request = api
+            .agent
+            .post(&format!("{}/releases", self.project_url()))
+            .set("PRIVATE-TOKEN", &api.token);
+        api.send_json(
+            request,
+            json!({"tag_name": tag, "name": tag, "description": body}),
+        )?;
+        Ok(tag.to_owned())
+    }
+
+    fn upload_asset(&self, release: &str, path: &Path) -> Result<()> {
+        let api = &self.0;
+        let request = api
+            .agent
+            .post(&format!("{}/uploads", self.project_url()))
+            .set("PRIVATE-TOKEN", &api.token);
+        let upload = api.send_multipart(request, "file", path)?;
+        let upload_path = upload["full_path"]
+            .as_str()
+            .ok_or_else(|| format_err!("Upload response is missing full_path"))?;
+
+        let request = api
+            .agent
+            .post(&format!(
+                "{}/releases/{}/assets/links",
+                self.project_url(),
+                encode(release)
+            ))
+            .set("PRIVATE-TOKEN", &api.token);
+        api.send_json(
+            request,
+            json!({"name": file_name(path), "url": format!("{}{upload_path}", api.base_url)}),
+        )
+        .map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::changelog_section;
+
+    const CHANGELOG: &str = "# Changelog\n\n## v1.2.0\n* Added forges\n\n### Fixes\n* Fixed things\n\n## [1.1.0] - 2024-01-01\n* Older\n";
+
+    #[test]
+    fn test_changelog_section() {
+        assert_eq!(
+            changelog_section(CHANGELOG, "1.2.0").unwrap(),
+            "* Added forges\n\n### Fixes\n* Fixed things"
+        );
+        assert_eq!(changelog_section(CHANGELOG, "1.1.0").unwrap(), "* Older");
+        assert_eq!(changelog_section(CHANGELOG, "1.0.0"), None);
+        assert_eq!(changelog_section(CHANGELOG, "1.2"), None);
+    }
+}
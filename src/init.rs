@@ -0,0 +1,245 @@
+use crate::{
+    commands::ConfigureOptions,
+    config::{Config, DiscoveryMode, CONFIG_FILENAME, SCHEMA_VERSION},
+    error::PargitError,
+    output,
+    project_types::rust::find_cargo_tomls,
+    repo::Repository,
+    utils::{can_ask_questions, get_color_theme, PathExt},
+};
+
+use anyhow::{bail, Context, Result};
+use dialoguer::{Confirm, Input};
+use log::{info, warn};
+use semver::Version;
+use std::path::{Path, PathBuf};
+use toml_edit::{table, value, DocumentMut};
+
+const DOCUMENTATION_URL: &str = "https://github.com/vmalloc/pargit#configuration";
+
+/// Bootstraps pargit in the repository at `repo_path`: detects (or asks for) the branch names, project layout and tag
+/// prefix, creates missing branches and writes a minimal .pargit.toml
+pub fn configure(repo_path: &Path, options: &ConfigureOptions) -> Result<()> {
+    let repo = Repository::on_path(repo_path)?;
+    let wizard = Wizard {
+        interactive: !options.non_interactive && can_ask_questions(),
+    };
+
+    let config_path = repo.path().join(CONFIG_FILENAME);
+    if config_path.exists() && !options.force {
+        if !wizard.interactive {
+            bail!("{config_path:?} already exists. Use --force to overwrite it");
+        }
+        if !wizard.confirm(
+            &format!("{CONFIG_FILENAME} already exists. Overwrite it?"),
+            false,
+        )? {
+            bail!(PargitError::UserAborted);
+        }
+    }
+
+    if options.sample {
+        std::fs::write(&config_path, Config::sample()).context("Failed writing configuration")?;
+        info!("Successfully wrote {config_path:?}");
+        return Ok(());
+    }
+
+    let main_branch = match &options.main_branch {
+        Some(name) => name.clone(),
+        None => wizard.input(
+            "Main branch",
+            detect_main_branch(
+                &repo,
+                options.develop_branch.as_deref().unwrap_or("develop"),
+            ),
+        )?,
+    };
+    ensure_local_branch(&repo, &main_branch)?;
+
+    let develop_branch = match &options.develop_branch {
+        Some(name) => name.clone(),
+        None => wizard.input("Develop branch", "develop".into())?,
+    };
+    if repo.find_branch(&develop_branch).is_err() {
+        create_develop_branch(&repo, &wizard, options, &main_branch, &develop_branch)?;
+    }
+
+    let subpath = detect_project_subpath(&repo);
+
+    let tag_prefix = match &options.tag_prefix {
+        Some(prefix) => prefix.clone(),
+        None => wizard.input("Tag prefix", suggest_tag_prefix(&repo.tags()?))?,
+    };
+
+    let mut document = DocumentMut::new();
+    document["schema_version"] = value(i64::from(SCHEMA_VERSION));
+    document["main_branch_name"] = value(main_branch);
+    document["develop_branch_name"] = value(develop_branch);
+    if !tag_prefix.is_empty() {
+        document["tag_prefix"] = value(tag_prefix);
+    }
+    if let Some(subpath) = subpath {
+        document["project"] = table();
+        document["project"]["subpath"] = value(subpath.to_string_lossy().as_ref());
+    }
+    let contents = format!("# See {DOCUMENTATION_URL} for all available settings\n{document}");
+    std::fs::write(&config_path, contents).context("Failed writing configuration")?;
+
+    info!("Successfully wrote {config_path:?}");
+    Ok(())
+}
+
+struct Wizard {
+    interactive: bool,
+}
+
+impl Wizard {
+    /// Asks for a value, returning `default` right away when not interactive
+    fn input(&self, prompt: &str, default: String) -> Result<String> {
+        if !self.interactive {
+            return Ok(default);
+        }
+        Ok(Input::with_theme(get_color_theme().as_ref())
+            .with_prompt(prompt)
+            .default(default)
+            .allow_empty(true)
+            .interact_text()?)
+    }
+
+    fn confirm(&self, prompt: &str, default: bool) -> Result<bool> {
+        if !self.interactive {
+            return Ok(default);
+        }
+        Ok(Confirm::with_theme(get_color_theme().as_ref())
+            .with_prompt(prompt)
+            .default(default)
+            .interact()?)
+    }
+}
+
+fn detect_main_branch(repo: &Repository, develop_branch_name: &str) -> String {
+    repo.detect_main_branch("origin", develop_branch_name)
+        .map(|(name, _)| name)
+        .unwrap_or_else(|| "master".into())
+}
+
+/// Makes sure a local branch exists, creating it from its remote counterpart if needed
+fn ensure_local_branch(repo: &Repository, branch_name: &str) -> Result<()> {
+    if repo.find_branch(branch_name).is_ok() {
+        return Ok(());
+    }
+    if !repo.has_remote_branch("origin", branch_name) {
+        bail!("Branch {branch_name} exists neither locally nor on origin");
+    }
+    info!("Creating local branch {branch_name} tracking origin/{branch_name}");
+    repo.path()
+        .shell(format!("git branch {0} origin/{0}", branch_name))
+}
+
+fn create_develop_branch(
+    repo: &Repository,
+    wizard: &Wizard,
+    options: &ConfigureOptions,
+    main_branch: &str,
+    develop_branch: &str,
+) -> Result<()> {
+    if repo.has_remote_branch("origin", develop_branch) {
+        return ensure_local_branch(repo, develop_branch);
+    }
+    let create = options.create_develop
+        || wizard.interactive
+            && wizard.confirm(
+                &format!(
+                    "{develop_branch} branch does not exist. Create it from {main_branch} and push it?"
+                ),
+                true,
+            )?;
+    if !create {
+        warn!("{develop_branch} branch does not exist. Create it before using pargit, or rerun with --create-develop");
+        return Ok(());
+    }
+
+    info!("Creating {develop_branch} branch from {main_branch}");
+    repo.create_branch(develop_branch, Some(main_branch), false)?;
+    output::record(|report| report.created_branches.push(develop_branch.to_owned()));
+    if repo.remote_url("origin").is_none() {
+        warn!("No origin remote configured. Not pushing {develop_branch}");
+        return Ok(());
+    }
+    info!("Pushing {develop_branch} to origin...");
+    repo.path()
+        .shell(format!("git push -u origin {0}:{0}", develop_branch))?;
+    output::record(|report| {
+        report
+            .pushed_refs
+            .push(format!("refs/heads/{develop_branch}"))
+    });
+    Ok(())
+}
+
+/// Looks for version files, returning the project's directory when it isn't the repository root
+fn detect_project_subpath(repo: &Repository) -> Option<PathBuf> {
+    let version_files = match find_cargo_tomls(repo, repo.path(), repo.path(), DiscoveryMode::Walk)
+    {
+        Ok(version_files) => version_files,
+        Err(_) => {
+            info!("No version files found. Releases will be tracked by tags alone");
+            return None;
+        }
+    };
+    for version_file in &version_files {
+        info!(
+            "Found {} {:?} (version {})",
+            version_file.typename(),
+            version_file
+                .path()
+                .strip_prefix(repo.path())
+                .unwrap_or(version_file.path()),
+            version_file.version()
+        );
+    }
+
+    // the shallowest version file is the project's root, as long as all others reside within its directory
+    let root = version_files
+        .iter()
+        .filter_map(|version_file| version_file.path().parent())
+        .min_by_key(|dir| dir.components().count())?;
+    if version_files
+        .iter()
+        .any(|version_file| !version_file.path().starts_with(root))
+    {
+        warn!("Found version files in several directories. Configure `projects` to release each of them separately");
+        return None;
+    }
+    root.strip_prefix(repo.path())
+        .ok()
+        .filter(|subpath| !subpath.as_os_str().is_empty())
+        .map(Path::to_owned)
+}
+
+/// Suggests "v" when most existing version tags use it (e.g. v1.2.0), and no prefix otherwise
+fn suggest_tag_prefix(tags: &[String]) -> String {
+    let is_version = |s: &str| Version::parse(s).is_ok();
+    let prefixed = tags
+        .iter()
+        .filter(|tag| tag.strip_prefix('v').is_some_and(is_version))
+        .count();
+    let bare = tags.iter().filter(|tag| is_version(tag)).count();
+    if prefixed > bare { "v" } else { "" }.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::suggest_tag_prefix;
+
+    #[test]
+    fn test_suggest_tag_prefix() {
+        let tags = |tags: &[&str]| tags.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(suggest_tag_prefix(&tags(&[])), "");
+        assert_eq!(
+            suggest_tag_prefix(&tags(&["v1.0.0", "v1.1.0", "1.2.0"])),
+            "v"
+        );
+        assert_eq!(suggest_tag_prefix(&tags(&["1.0.0", "v1", "latest"])), "");
+    }
+}
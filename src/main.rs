@@ -1,20 +1,29 @@
-use std::{io::Write, path::PathBuf};
+use std::path::PathBuf;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use clap::Parser;
-use commands::{FlowCommand, ReleaseCommand, VersionCommand};
+use commands::{ConfigCommand, FlowCommand, ReleaseCommand, VersionCommand};
 use log::error;
+use output::OutputFormat;
 use pargit::Pargit;
 use utils::ObjectKind;
 
+mod cleanup;
 mod commands;
 mod config;
+mod doctor;
+mod error;
+mod forge;
+mod init;
+mod metadata;
+mod output;
 mod pargit;
 mod project_types;
 mod release;
 mod repo;
 mod utils;
 mod version_file;
+mod webhooks;
 
 #[derive(Parser)]
 #[command(version)]
@@ -32,40 +41,122 @@ struct Opts {
     /// Path of the repository to operate on
     path: PathBuf,
 
+    #[clap(global = true, long = "project", env = "PARGIT_PROJECT")]
+    /// Name of the project to operate on, for repositories configuring multiple projects
+    project: Option<String>,
+
+    #[clap(global = true, long = "output", value_enum, default_value_t)]
+    /// Output format. JSON output suppresses logging and interactive questions, and prints the command's results
+    output: OutputFormat,
+
+    #[clap(global = true, long = "autostash")]
+    /// Stashes local changes before operating, and restores them afterwards
+    autostash: bool,
+
     #[clap(subcommand)]
     command: commands::Command,
 }
 
+impl Opts {
+    /// Configuration values set through command line flags, taking precedence over all other configuration sources
+    fn config_overrides(&self) -> toml::value::Table {
+        let mut returned = toml::value::Table::new();
+        if self.autostash {
+            returned.insert("autostash".into(), true.into());
+        }
+        returned
+    }
+}
+
 fn entry_point(opts: Opts) -> Result<()> {
     log::debug!("Starting...");
 
     use commands::Command::*;
 
-    if let Configure = opts.command {
-        process_configure_command()
+    if let Configure(options) = &opts.command {
+        init::configure(&opts.path, options)
+    } else if let commands::Command::Config(cmd) = &opts.command {
+        process_config_command(&opts, cmd)
+    } else if let Doctor = opts.command {
+        let project = Pargit::open(&opts.path, opts.project.as_deref(), opts.config_overrides())?;
+        doctor::run(&project, opts.output != OutputFormat::Human)
     } else {
-        let project = Pargit::new(&opts.path)?;
-
-        match opts.command {
-            Configure => Ok(()),
-            Release(cmd) => process_release_command(&project, cmd, ObjectKind::Release),
-            Hotfix(cmd) => process_release_command(&project, cmd, ObjectKind::Hotfix),
-            Feature(cmd) => process_flow_command(&project, ObjectKind::Feature, cmd),
-            Bugfix(cmd) => process_flow_command(&project, ObjectKind::Bugfix, cmd),
-            commands::Command::Version(VersionCommand::Bump { kind }) => project.bump_version(kind),
-            Cleanup => project.pargit_cleanup(),
+        let project = Pargit::new(&opts.path, opts.project.as_deref(), opts.config_overrides())?;
+
+        if opts.command.modifies_work_tree() {
+            let autostash = project.config().autostash;
+            project.with_clean_work_tree(autostash, || {
+                process_command(&project, opts.command, opts.output)
+            })
+        } else {
+            process_command(&project, opts.command, opts.output)
         }
     }
 }
 
-fn process_configure_command() -> Result<()> {
-    let filename = ".pargit.toml";
-    std::fs::File::create_new(filename)
-        .context("Failed creating config file")?
-        .write_all(crate::config::Config::sample().as_bytes())
-        .context("Failed writing configuration")?;
+fn process_command(
+    project: &Pargit,
+    command: commands::Command,
+    output: OutputFormat,
+) -> Result<()> {
+    use commands::Command::*;
 
-    log::info!("Successfully wrote {filename}");
+    match command {
+        Configure(_) => Ok(()),
+        Release(cmd) => process_release_command(project, cmd, ObjectKind::Release, output),
+        Hotfix(cmd) => process_release_command(project, cmd, ObjectKind::Hotfix, output),
+        Feature(cmd) => process_flow_command(project, ObjectKind::Feature, cmd),
+        Bugfix(cmd) => process_flow_command(project, ObjectKind::Bugfix, cmd),
+        commands::Command::Version(VersionCommand::Bump { kind, selection }) => {
+            project.bump_version(kind, &selection)
+        }
+        Cleanup(options) => cleanup::run(project, &options),
+        Doctor => Ok(()),
+        commands::Command::Config(_) => Ok(()),
+    }
+}
+
+fn process_config_command(opts: &Opts, cmd: &ConfigCommand) -> Result<()> {
+    match cmd {
+        ConfigCommand::Show { origin } => {
+            let mut config = config::Config::load(&opts.path, opts.config_overrides())?;
+            if let Some(project) = &opts.project {
+                config.select_project(Some(project))?;
+            }
+            let values = config.effective_values()?;
+            if opts.output == OutputFormat::Human {
+                for (key, value, value_origin) in &values {
+                    if *origin {
+                        println!("{key} = {value}  # {value_origin}");
+                    } else {
+                        println!("{key} = {value}");
+                    }
+                }
+            }
+            output::record(|report| {
+                report.config = Some(
+                    values
+                        .into_iter()
+                        .map(|(key, value, origin)| output::ConfigEntry {
+                            key,
+                            value,
+                            origin: origin.to_string(),
+                        })
+                        .collect(),
+                )
+            });
+        }
+        ConfigCommand::Migrate { file } => {
+            let path = file
+                .clone()
+                .unwrap_or_else(|| opts.path.join(config::CONFIG_FILENAME));
+            if config::migrate_file(&path)? {
+                log::info!("Migrated {path:?}");
+            } else {
+                log::info!("{path:?} is already up to date");
+            }
+        }
+    }
     Ok(())
 }
 
@@ -73,26 +164,42 @@ fn process_release_command(
     project: &Pargit,
     cmd: ReleaseCommand,
     release_kind: ObjectKind,
+    output: OutputFormat,
 ) -> Result<()> {
     use commands::ReleaseCommand::*;
 
     match cmd {
-        Start { spec, from_ref } => project
-            .release_start(spec, release_kind, from_ref.as_deref())
+        Start {
+            spec,
+            from_ref,
+            selection,
+            force,
+        } => project
+            .release_start(spec, release_kind, from_ref.as_deref(), &selection, force)
             .map(drop),
         Publish { name } => project.pargit_publish(release_kind, name),
         ReleaseCommand::Delete { name } => project.pargit_delete(release_kind, name),
         Finish { name, options } => project.release_finish(name, None, release_kind, options),
-        ReleaseCommand::Version { spec, options } => {
-            project.release_version(spec, release_kind, options)
-        }
+        Complete { name } => project.release_complete(name, release_kind),
+        Yank { version, revert } => project.release_yank(release_kind, &version, revert),
+        Info { version } => project.release_info(&version, output != OutputFormat::Human),
+        ReleaseCommand::Version {
+            spec,
+            options,
+            selection,
+        } => project.release_version(spec, release_kind, options, &selection),
     }
 }
 
 fn process_flow_command(project: &Pargit, kind: ObjectKind, cmd: FlowCommand) -> Result<()> {
     match cmd {
         FlowCommand::Delete { name } => project.pargit_delete(kind, name),
-        FlowCommand::Start { name, from_ref } => {
+        FlowCommand::Start {
+            name,
+            from_ref,
+            slugify,
+        } => {
+            let name = if slugify { utils::slugify(&name) } else { name };
             project.pargit_start(kind, &name, from_ref.as_deref())
         }
         FlowCommand::Publish { name } => project.pargit_publish(kind, name),
@@ -104,20 +211,33 @@ fn process_flow_command(project: &Pargit, kind: ObjectKind, cmd: FlowCommand) ->
 
 fn main() {
     let opts = Opts::parse();
+    let output = opts.output;
+
+    if output == OutputFormat::Json {
+        std::env::set_var("PARGIT_NON_INTERACTIVE", "1");
+    }
 
     env_logger::Builder::new()
-        .filter_level(match (opts.verbosity + 2).saturating_sub(opts.quietness) {
-            0 => log::LevelFilter::Error,
-            1 => log::LevelFilter::Warn,
-            2 => log::LevelFilter::Info,
-            _ => log::LevelFilter::Debug,
+        .filter_level(match output {
+            OutputFormat::Json => log::LevelFilter::Off,
+            OutputFormat::Human => match (opts.verbosity + 2).saturating_sub(opts.quietness) {
+                0 => log::LevelFilter::Error,
+                1 => log::LevelFilter::Warn,
+                2 => log::LevelFilter::Info,
+                _ => log::LevelFilter::Debug,
+            },
         })
         .format_timestamp(None)
         .format_module_path(false)
         .init();
 
-    if let Err(e) = entry_point(opts) {
+    let result = entry_point(opts);
+    if output == OutputFormat::Json {
+        output::print_json(&result);
+    } else if let Err(e) = &result {
         error!("{:?}", e);
-        std::process::exit(-1);
+    }
+    if let Err(e) = &result {
+        std::process::exit(error::exit_code(e));
     }
 }
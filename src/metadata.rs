@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// The notes reference release metadata is recorded under
+pub const NOTES_REF: &str = "refs/notes/pargit";
+
+/// Who released what, when and how, attached as a (TOML) git note to the commit of each release tag
+#[derive(Deserialize, Serialize)]
+pub struct ReleaseMetadata {
+    pub name: String,
+    /// "release" or "hotfix"
+    pub kind: String,
+    pub version: String,
+    pub tag: String,
+    /// The git identity of the releaser, e.g. "Jane Doe <jane@example.com>"
+    pub released_by: Option<String>,
+    /// RFC 3339 UTC timestamp
+    pub released_at: String,
+    /// Name of the machine the release was made on
+    pub host: Option<String>,
+    pub pargit_version: String,
+    /// Pre-release checks that passed before the release was finished
+    pub checks: Vec<String>,
+}
+
+impl std::fmt::Display for ReleaseMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unknown = "unknown";
+        let mut kind = self.kind.chars();
+        let kind = kind
+            .next()
+            .map(|first| first.to_uppercase().chain(kind).collect::<String>())
+            .unwrap_or_default();
+        writeln!(
+            f,
+            "{:<16}{} (tag {})",
+            format!("{kind}:"),
+            self.name,
+            self.tag
+        )?;
+        writeln!(
+            f,
+            "Released by:    {}",
+            self.released_by.as_deref().unwrap_or(unknown)
+        )?;
+        writeln!(f, "Released at:    {}", self.released_at)?;
+        writeln!(
+            f,
+            "Host:           {}",
+            self.host.as_deref().unwrap_or(unknown)
+        )?;
+        writeln!(f, "Pargit version: {}", self.pargit_version)?;
+        write!(
+            f,
+            "Checks passed:  {}",
+            if self.checks.is_empty() {
+                "none".to_owned()
+            } else {
+                self.checks.join(", ")
+            }
+        )
+    }
+}
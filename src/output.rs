@@ -0,0 +1,118 @@
+use crate::{metadata::ReleaseMetadata, release::Release};
+
+use serde::Serialize;
+use std::{path::PathBuf, sync::Mutex};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Log messages meant for humans
+    #[default]
+    Human,
+    /// Print a single JSON document describing the command's results to stdout
+    Json,
+}
+
+/// What a command did, reported when using JSON output
+#[derive(Serialize)]
+pub struct Report {
+    pub created_branches: Vec<String>,
+    pub release: Option<ReleaseReport>,
+    pub bumped_files: Vec<PathBuf>,
+    pub pushed_refs: Vec<String>,
+    /// Deleted local branches, and remote branches (e.g. origin/feature/x)
+    pub deleted_branches: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<Vec<ConfigEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checks: Option<Vec<CheckReport>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_branches: Option<Vec<StaleBranchReport>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ReleaseMetadata>,
+}
+
+#[derive(Serialize)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: toml::Value,
+    pub origin: String,
+}
+
+/// The outcome of a single `pargit doctor` check
+#[derive(Serialize)]
+pub struct CheckReport {
+    pub name: String,
+    pub ok: bool,
+    /// What's wrong, for failed checks
+    pub problem: Option<String>,
+    /// How to fix the problem, for failed checks
+    pub hint: Option<String>,
+}
+
+/// An unfinished release or hotfix branch found by `pargit cleanup`
+#[derive(Serialize)]
+pub struct StaleBranchReport {
+    pub name: String,
+    /// Days since the branch's latest commit
+    pub days: u64,
+}
+
+#[derive(Serialize)]
+pub struct ReleaseReport {
+    pub name: String,
+    pub version: String,
+    pub tag: String,
+}
+
+impl From<&Release> for ReleaseReport {
+    fn from(release: &Release) -> Self {
+        Self {
+            name: release.name.clone(),
+            version: release.version.to_string(),
+            tag: release.tag.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorReport {
+    code: &'static str,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonOutput<'a> {
+    ok: bool,
+    #[serde(flatten)]
+    report: &'a Report,
+    error: Option<ErrorReport>,
+}
+
+static REPORT: Mutex<Report> = Mutex::new(Report {
+    created_branches: Vec::new(),
+    release: None,
+    bumped_files: Vec::new(),
+    pushed_refs: Vec::new(),
+    deleted_branches: Vec::new(),
+    config: None,
+    checks: None,
+    stale_branches: None,
+    metadata: None,
+});
+
+pub fn record(f: impl FnOnce(&mut Report)) {
+    f(&mut REPORT.lock().unwrap())
+}
+
+pub fn print_json(result: &anyhow::Result<()>) {
+    let report = REPORT.lock().unwrap();
+    let output = JsonOutput {
+        ok: result.is_ok(),
+        report: &report,
+        error: result.as_ref().err().map(|e| ErrorReport {
+            code: crate::error::error_code(e),
+            message: format!("{e:#}"),
+        }),
+    };
+    println!("{}", serde_json::to_string(&output).unwrap());
+}
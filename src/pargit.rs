@@ -1,12 +1,17 @@
 use crate::{
-    commands::{BumpKind, ReleaseOptions, VersionSpec},
-    config::Config,
+    commands::{BumpKind, ReleaseOptions, VersionFileSelection, VersionSpec},
+    config::{render_template, AliasTag, Config, LockRefresh, ProjectKind},
+    error::PargitError,
+    metadata::{ReleaseMetadata, NOTES_REF},
+    output::{self, ReleaseReport},
     release::Release,
-    repo::Repository,
+    repo::{remote_notes_ref, Repository},
     utils::{
-        can_ask_questions, get_color_theme, next_version, ExitStack, ObjectKind, PathExt, ResultExt,
+        can_ask_questions, check_branch_name, format_utc_timestamp, get_color_theme, next_version,
+        ExitStack, ObjectKind, PathExt, ResultExt,
     },
     version_file::VersionFile,
+    webhooks::WebhookPayload,
 };
 use anyhow::{bail, format_err, Context, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Select};
@@ -16,8 +21,22 @@ use semver::Version;
 use std::{
     collections::HashSet,
     path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
+/// Prefix of the temporary branches releases are merged into the main branch on
+pub(crate) const TEMP_BRANCH_PREFIX: &str = "pargit-in-progress-";
+
+/// Prefix of the tags yanked releases are recorded by, e.g. yanked/v1.2.0
+pub(crate) const YANKED_TAG_PREFIX: &str = "yanked/";
+
+/// Git branch configuration key remembering the tag of a release pushed for merging into a protected main branch
+const PENDING_TAG_CONFIG_KEY: &str = "pargitTag";
+
+/// Git branch configuration key remembering the pre-release checks passed by a release pushed for merging into a
+/// protected main branch
+const PENDING_CHECKS_CONFIG_KEY: &str = "pargitChecks";
+
 pub struct Pargit {
     repo_path: PathBuf,
     project_path: PathBuf,
@@ -27,37 +46,87 @@ pub struct Pargit {
 }
 
 impl Pargit {
-    pub fn new(repo_path: &Path) -> Result<Self> {
-        let config = Config::load(repo_path)?;
-
-        let project_path = repo_path.join(
+    pub fn new(
+        repo_path: &Path,
+        project_name: Option<&str>,
+        config_overrides: toml::value::Table,
+    ) -> Result<Self> {
+        let returned = Self::open(repo_path, project_name, config_overrides)?;
+        returned.check_configuration()?;
+        Ok(returned)
+    }
 
-            if let Some(p) = &config.project_subpath {
-                log::warn!("project_subpath configuration parameter is deprecated. Use project.subpath instead")   ;
-                p.clone()
-            } else {
-                config.project_config.subpath.as_ref().cloned().unwrap_or_else(||PathBuf::from("."))
-            });
+    /// Opens the project without checking that the configured branches exist, e.g. for diagnosing problems
+    pub fn open(
+        repo_path: &Path,
+        project_name: Option<&str>,
+        config_overrides: toml::value::Table,
+    ) -> Result<Self> {
+        let mut config = Config::load(repo_path, config_overrides)?;
+        config.select_project(project_name)?;
 
-        let type_ = if project_path.join("Cargo.toml").exists() {
-            Some(ProjectType::Rust)
+        let project_path = repo_path.join(if let Some(p) = &config.project_subpath {
+            p.clone()
         } else {
-            None
+            config
+                .project_config
+                .subpath
+                .as_ref()
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from("."))
+        });
+
+        let type_ = match config.project_config.kind {
+            Some(ProjectKind::Rust) => Some(ProjectType::Rust),
+            Some(ProjectKind::Generic) => None,
+            None if project_path.join("Cargo.toml").exists() => Some(ProjectType::Rust),
+            None => None,
         };
         let repo = Repository::on_path(repo_path)?;
-        let returned = Self {
+        Ok(Self {
             repo_path: repo_path.canonicalize()?,
             project_path: project_path.canonicalize()?,
             config,
             repo,
             type_,
-        };
-        returned.check_configuration()?;
-        Ok(returned)
+        })
+    }
+
+    /// Runs an operation requiring a clean working tree. With `autostash`, local changes are stashed before the
+    /// operation and restored after it on whichever branch it ended on, whether it succeeded or was rolled back. If
+    /// restoring them conflicts, they are kept in the stash
+    pub fn with_clean_work_tree<T>(
+        &self,
+        autostash: bool,
+        operation: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        if !self.repo.is_dirty()? {
+            return operation();
+        }
+        if !autostash {
+            bail!(PargitError::DirtyTree);
+        }
+        info!("Stashing local changes");
+        if !self.repo.stash_push("pargit autostash")? {
+            return operation();
+        }
+        let result = operation();
+        info!("Restoring stashed changes");
+        if let Err(e) = self.repo.stash_pop() {
+            warn!("Failed restoring stashed changes. They are kept in the stash, use `git stash pop` to restore them: {e}");
+        }
+        result
     }
 
     pub fn check_configuration(&self) -> Result<()> {
-        self.repo.find_branch(&self.config.develop_branch_name)?;
+        self.repo
+            .find_branch(&self.config.develop_branch_name)
+            .with_context(|| {
+                format!(
+                    "{} branch not found. Run `pargit configure` to create it",
+                    self.config.develop_branch_name
+                )
+            })?;
         self.ensure_main_branch().map(drop)
     }
 
@@ -66,15 +135,16 @@ impl Pargit {
             Ok(_) => Ok(()),
             Err(e) => {
                 if let Some(e) = e.downcast_ref::<git2::Error>() {
-                    if e.code() == ErrorCode::NotFound
-                        && can_ask_questions()
-                        && Confirm::with_theme(get_color_theme().as_ref())
+                    if e.code() == ErrorCode::NotFound && can_ask_questions() {
+                        if !Confirm::with_theme(get_color_theme().as_ref())
                             .with_prompt(format!(
                                 "{} branch not found. Create it?",
                                 self.config().main_branch_name
                             ))
                             .interact()?
-                    {
+                        {
+                            bail!(PargitError::UserAborted);
+                        }
                         self.repo.path().shell(format!(
                             "git branch {0} origin/{0}",
                             self.config().main_branch_name
@@ -89,29 +159,37 @@ impl Pargit {
 
     // High-level API
 
-    pub fn bump_version(&self, bump_kind: BumpKind) -> Result<()> {
+    pub fn bump_version(
+        &self,
+        bump_kind: BumpKind,
+        selection: &VersionFileSelection,
+    ) -> Result<()> {
         debug!("Bumping version: {:?}", bump_kind);
 
-        let files_to_bump = self.get_version_files_to_bump()?;
+        let files_to_bump = self.get_version_files_to_bump(selection)?;
 
         if files_to_bump.is_empty() {
             bail!("Could not find version files to bump");
         }
 
-        for bumped_file in files_to_bump {
+        for bumped_file in &files_to_bump {
             debug!("Bumping version file {bumped_file:?}...");
             bumped_file.bump(VersionSpec::Bump(bump_kind))?;
         }
+        self.record_bumped_files(&files_to_bump);
 
-        info!("Compiling project to lock version");
-        self.compile()
+        self.lock_versions(&files_to_bump)
     }
 
-    pub fn pargit_cleanup(&self) -> Result<()> {
-        self.repo.cleanup(&self.config.develop_branch_name)
+    pub fn pargit_delete(&self, kind: ObjectKind, name: Option<String>) -> Result<()> {
+        let name = self.resolve_name(kind, name)?;
+        let commit = self.repo.resolve_commit(&self.prefix(kind, &name))?;
+        self.delete_branch(kind, Some(name.clone()))?;
+        self.notify_webhooks("delete", kind, &name, commit, None, None);
+        Ok(())
     }
 
-    pub fn pargit_delete(&self, kind: ObjectKind, name: Option<String>) -> Result<()> {
+    fn delete_branch(&self, kind: ObjectKind, name: Option<String>) -> Result<()> {
         let release_name = self.resolve_name(kind, name)?;
         let branch_name = self.prefix(kind, &release_name);
         info!("Deleting {branch_name}...");
@@ -154,14 +232,22 @@ impl Pargit {
         name: Option<String>,
         dest_branch: &str,
     ) -> Result<()> {
+        self.check_signing()?;
         let name = self.resolve_name(kind, name)?;
         debug!("Switching to branch {}", dest_branch);
         self.repo.switch_to_branch_name(dest_branch)?;
         let branch_name = self.prefix(kind, &name);
+        let message = self.message_context(kind, &name, &branch_name, dest_branch, "", "")?;
         debug!("Merging {}", branch_name);
-        self.repo
-            .merge_branch_name(&branch_name, &format!("Merge {}", branch_name))?;
-        self.pargit_delete(kind, Some(name))
+        self.repo.merge_branch_name(
+            &branch_name,
+            &message.render(&self.config.messages.feature_merge)?,
+            self.config.sign_commits,
+        )?;
+        let commit = self.repo.resolve_commit("HEAD")?;
+        self.delete_branch(kind, Some(name.clone()))?;
+        self.notify_webhooks("finish", kind, &name, commit, None, None);
+        Ok(())
     }
 
     pub fn pargit_publish(&self, kind: ObjectKind, name: Option<String>) -> Result<()> {
@@ -171,6 +257,7 @@ impl Pargit {
         let output = self
             .repo_path
             .shell_output(format!("git push -u origin {0}:{0}", branch_name))?;
+        self.record_push(format!("refs/heads/{branch_name}"));
         for line in String::from_utf8_lossy(&output.stderr).lines() {
             if line.starts_with("remote:") {
                 info!("{}", line);
@@ -180,9 +267,14 @@ impl Pargit {
     }
 
     pub fn pargit_start(&self, kind: ObjectKind, name: &str, from_ref: Option<&str>) -> Result<()> {
-        info!("Creating {} branch {}", kind, name);
         let branch_name = self.prefix(kind, name);
-        if self.repo.find_branch(branch_name).is_ok() {
+        check_branch_name(&branch_name)
+            .map_err(|reason| format_err!("Invalid {kind} name {name:?}: {reason}"))?;
+        if kind == ObjectKind::Feature {
+            self.check_feature_name(name)?;
+        }
+        info!("Creating {} branch {}", kind, name);
+        if self.repo.find_branch(&branch_name).is_ok() {
             bail!("{} {} already in progress. Finish it first", kind, name);
         }
 
@@ -195,7 +287,8 @@ impl Pargit {
 
         let b = self
             .repo
-            .create_branch(self.prefix(kind, name), Some(start_point), false)?;
+            .create_branch(&branch_name, Some(start_point), false)?;
+        output::record(|report| report.created_branches.push(branch_name));
 
         self.repo.switch_to_branch(&b)
     }
@@ -205,26 +298,42 @@ impl Pargit {
         spec: VersionSpec,
         release_kind: ObjectKind,
         options: ReleaseOptions,
+        selection: &VersionFileSelection,
     ) -> Result<()> {
+        self.check_signing()?;
         let start_point = release_kind.get_start_point(self, None)?;
         if !self.repo.is_branch_up_to_date(start_point)? {
             if options.no_pull {
-                bail!("Local {0} branch is behind remote {0} branch. Update your local {0} branch before creating a release.", start_point);
+                bail!(PargitError::BehindRemote(start_point.to_owned()));
             }
             info!("Pulling {start_point} branch before creating {release_kind}");
             self.repo.pull_branch_from_remote(start_point, true)?;
         }
 
         let mut history = ExitStack::default();
-        let release = self.release_start(spec, release_kind, None)?;
+        let release = self.release_start(spec, release_kind, None, selection, options.force)?;
         let release_name = release.name.clone();
         let release_name_clone = release.name.clone();
         history.remember(format!("Delete {} branch", release_kind), move || {
-            self.pargit_delete(release_kind, Some(release_name_clone))
+            self.delete_branch(release_kind, Some(release_name_clone))
                 .ignore_errors()
         });
         if self.repo.is_dirty()? {
-            self.repo.commit_all("pargit: Bump version")?;
+            let mut message = self.message_context(
+                release_kind,
+                &release.name,
+                &self.prefix(release_kind, &release.name),
+                start_point,
+                &release.version.to_string(),
+                &release.tag,
+            )?;
+            if let Some(version_file) = release.version_files.iter().flatten().next() {
+                message.previous_version = version_file.version().to_string();
+            }
+            self.repo.commit_all(
+                &message.render(&self.config.messages.bump_commit)?,
+                self.config.sign_commits,
+            )?;
         }
         self.release_finish(
             Some(release_name),
@@ -241,26 +350,38 @@ impl Pargit {
         spec: VersionSpec,
         kind: ObjectKind,
         from_ref: Option<&str>,
+        selection: &VersionFileSelection,
+        force: bool,
     ) -> Result<Release> {
-        let release = self.resolve_release(spec)?;
+        let release = self.resolve_release(spec, selection)?;
         let mut undo = ExitStack::default();
 
         if self.repo.has_tag(&release.tag)? {
-            bail!("Tag {} already exists", release.tag);
+            bail!(PargitError::TagExists(format!(
+                "Tag {} already exists",
+                release.tag
+            )));
+        }
+        self.check_remote_tag(&release.tag)?;
+        if force {
+            warn!("Skipping version consistency checks");
+        } else {
+            self.check_release_version(&release, kind)?;
         }
         self.pargit_start(kind, &release.name, from_ref)?;
         undo.remember("Deleting release branch", || {
-            self.pargit_delete(kind, None).ignore_errors()
+            self.delete_branch(kind, None).ignore_errors()
         });
         if let Some(version_files) = release.version_files.as_ref() {
             for file in version_files {
                 file.bump(VersionSpec::Exact(release.version.clone()))?;
             }
-            info!("Compiling project to lock new version");
-            self.compile()
-                .inspect_err(|err| debug!("Compilation failed on: {err}"))?;
+            self.record_bumped_files(version_files);
+            self.lock_versions(version_files)
+                .inspect_err(|err| debug!("Locking versions failed on: {err}"))?;
         }
         undo.forget();
+        output::record(|report| report.release = Some((&release).into()));
         Ok(release)
     }
 
@@ -271,39 +392,67 @@ impl Pargit {
         release_kind: ObjectKind,
         options: ReleaseOptions,
     ) -> Result<()> {
+        self.check_signing()?;
         let release_name = self.resolve_name(release_kind, release_name)?;
         let release_branch_name = self.prefix(release_kind, &release_name);
         info!("Finishing {} {}", release_kind, release_name);
         self.repo.switch_to_branch_name(&release_branch_name)?;
-        self.check_pre_release(&options)
+        let checks = self
+            .check_pre_release(&options)
             .inspect_err(|err| debug!("Pre release checks failed: {err}"))?;
 
-        let temp_branch_name = format!("pargit-in-progress-{}-{}", release_kind, release_name);
+        let temp_branch_name = format!("{TEMP_BRANCH_PREFIX}{release_kind}-{release_name}");
 
         self.repo
             .create_branch(&temp_branch_name, Some(&self.config.main_branch_name), true)?;
         info!("Switching to temporary branch");
         self.repo.switch_to_branch_name(&temp_branch_name)?;
+        let version = self.config.release_name_version(&release_name).to_owned();
+        let tag = tag
+            .map(String::from)
+            .unwrap_or_else(|| self.config.get_tag_name(&version, None));
+        let message = self.message_context(
+            release_kind,
+            &release_name,
+            &release_branch_name,
+            &temp_branch_name,
+            &version,
+            &tag,
+        )?;
+        self.record_release(&release_name, &version, &tag);
         info!("Merging {} branch", release_kind);
         self.repo
             .merge_branch_name(
                 &release_branch_name,
-                &format!("Merge {} branch {}", release_kind, release_name),
+                &message.render(&self.config.messages.release_merge)?,
+                self.config.sign_commits,
             )
             .context("Failed merge")?;
+        if self.config.protected_main_branch {
+            return self.propose_release(
+                release_kind,
+                &release_branch_name,
+                &temp_branch_name,
+                &tag,
+                &checks,
+            );
+        }
+
         info!("Creating tag and pushing to remote main branch");
-        let tag = tag
-            .map(String::from)
-            .unwrap_or_else(|| self.config.get_tag_name(&release_name, None));
-        let res = self
-            .repo
-            .create_tag(&tag)
+        let tag_message = message.render(&self.config.messages.tag);
+        let res = tag_message
+            .and_then(|tag_message| {
+                self.repo
+                    .create_tag(&tag, &tag_message, self.config.sign_tags)
+            })
             .and_then(|_| {
-                self.repo_path.shell(format!(
-                    "git push origin {}:{}",
-                    temp_branch_name,
-                    self.config().main_branch_name
-                ))
+                self.repo_path
+                    .shell(format!(
+                        "git push origin {}:{}",
+                        temp_branch_name,
+                        self.config().main_branch_name
+                    ))
+                    .context(PargitError::PushRejected(format!("new {release_kind}")))
             })
             .context("Failed tag and push");
 
@@ -321,34 +470,539 @@ impl Pargit {
                 .delete_branch_name(&temp_branch_name)
                 .map_err(|e| error!("Failed deleting temporary branch: {:?}", e));
 
-            bail!("Failed pushing new {} - {:?}", release_kind, e);
+            return Err(e);
         }
 
+        self.record_push(format!("refs/heads/{}", self.config.main_branch_name));
         info!("Push successful. Merging to local master");
         self.repo
             .switch_to_branch_name(&self.config.main_branch_name)?;
+        // the temporary branch grew from the main branch, so this fast-forwards to the already pushed (and signed)
+        // merge rather than creating another commit
+        self.repo.merge_branch_name(
+            &temp_branch_name,
+            &message.render(&self.config.messages.release_merge)?,
+            false,
+        )?;
+        info!("Pushing tag");
+        // only the release's own tag, as other local tags (e.g. alias tags) may be older than the remote's
+        self.repo_path
+            .shell(format!("git push origin refs/tags/{tag}"))?;
+        self.record_push(format!("refs/tags/{tag}"));
+        self.merge_back_release(
+            release_kind,
+            release_name,
+            &temp_branch_name,
+            &tag,
+            message,
+            checks,
+        )
+    }
+
+    /// Pushes a merged release as its temporary branch instead of pushing the (protected) main branch directly. The
+    /// release is completed by `release_complete` once the branch is merged into the main branch
+    fn propose_release(
+        &self,
+        release_kind: ObjectKind,
+        release_branch_name: &str,
+        temp_branch_name: &str,
+        tag: &str,
+        checks: &[String],
+    ) -> Result<()> {
+        info!("Pushing {temp_branch_name} to remote");
+        let res = self
+            .repo
+            .set_branch_config(temp_branch_name, PENDING_TAG_CONFIG_KEY, tag)
+            .and_then(|_| {
+                self.repo.set_branch_config(
+                    temp_branch_name,
+                    PENDING_CHECKS_CONFIG_KEY,
+                    &checks.join(","),
+                )
+            })
+            .and_then(|_| {
+                self.repo_path
+                    .shell(format!("git push -u origin {0}:{0}", temp_branch_name))
+                    .context(PargitError::PushRejected(format!("new {release_kind}")))
+            });
+        if let Err(e) = res {
+            error!("Failed pushing {temp_branch_name}. Rolling back changes...");
+            let _ = self.repo.switch_to_branch_name(release_branch_name);
+            let _ = self
+                .repo
+                .delete_branch_name(temp_branch_name)
+                .map_err(|e| error!("Failed deleting temporary branch: {:?}", e));
+            return Err(e);
+        }
+        self.record_push(format!("refs/heads/{temp_branch_name}"));
+        self.repo.switch_to_branch_name(release_branch_name)?;
+        info!(
+            "Merge {temp_branch_name} into {} (e.g. through a pull request), then run `pargit {release_kind} complete`",
+            self.config.main_branch_name
+        );
+        Ok(())
+    }
+
+    /// Completes a release proposed through `propose_release`, once its branch was merged into the remote main branch.
+    /// This tags the release, merges it back to the develop branch and deletes the release branches
+    pub fn release_complete(
+        &self,
+        release_name: Option<String>,
+        release_kind: ObjectKind,
+    ) -> Result<()> {
+        self.check_signing()?;
+        let temp_branch_prefix = format!("{TEMP_BRANCH_PREFIX}{release_kind}-");
+        let release_name = match release_name {
+            Some(name) => name,
+            None => match self
+                .repo
+                .current_branch_name()?
+                .strip_prefix(&temp_branch_prefix)
+            {
+                Some(name) => name.to_owned(),
+                None => self.current_name(release_kind)?,
+            },
+        };
+        let release_branch_name = self.prefix(release_kind, &release_name);
+        let temp_branch_name = format!("{temp_branch_prefix}{release_name}");
+        info!("Completing {} {}", release_kind, release_name);
+
+        self.repo.git_fetch("origin")?;
+        let remote_main = format!("origin/{}", self.config.main_branch_name);
+        // protected branches are often squash or rebase merged, landing the release as different commits
+        let landed = self
+            .repo
+            .find_landed_commit(&temp_branch_name, &remote_main)?
+            .with_context(|| {
+                format!("{temp_branch_name} has not been merged into {remote_main} yet. Merge it first, then try again")
+            })?;
+
+        let version = self.config.release_name_version(&release_name).to_owned();
+        let tag = match self
+            .repo
+            .get_branch_config(&temp_branch_name, PENDING_TAG_CONFIG_KEY)?
+        {
+            Some(tag) => tag,
+            None => self.config.get_tag_name(&version, None),
+        };
+        let checks = self
+            .repo
+            .get_branch_config(&temp_branch_name, PENDING_CHECKS_CONFIG_KEY)?
+            .map(|checks| {
+                checks
+                    .split(',')
+                    .filter(|check| !check.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let message = self.message_context(
+            release_kind,
+            &release_name,
+            &release_branch_name,
+            &self.config.main_branch_name,
+            &version,
+            &tag,
+        )?;
+        self.record_release(&release_name, &version, &tag);
+
+        info!("Pulling {} branch", self.config.main_branch_name);
+        self.repo
+            .pull_branch_from_remote(&self.config.main_branch_name, true)?;
+        info!("Creating tag {tag}");
+        self.repo.create_tag_at(
+            &tag,
+            &landed,
+            &message.render(&self.config.messages.tag)?,
+            self.config.sign_tags,
+        )?;
+        info!("Pushing tag");
+        self.repo_path
+            .shell(format!("git push origin refs/tags/{tag}"))?;
+        self.record_push(format!("refs/tags/{tag}"));
+        self.merge_back_release(
+            release_kind,
+            release_name,
+            &temp_branch_name,
+            &tag,
+            message,
+            checks,
+        )?;
+        info!("Deleting remote branch {temp_branch_name}");
+        // forges commonly delete branches once their pull request is merged
+        self.repo_path
+            .shell(format!("git push origin :{temp_branch_name}"))
+            .or_else(|e| {
+                if format!("{e:?}").contains("remote ref does not exist") {
+                    warn!(
+                        "Remote branch {temp_branch_name} does not exist, skipping remote deletion"
+                    );
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            })
+    }
+
+    /// Withdraws a release: replaces its tag with a yanked/ tag, so that its version is neither considered the latest
+    /// release nor reused, and deletes the tag from the remote. Optionally reverts the changes the release brought into
+    /// the main and develop branches
+    pub fn release_yank(
+        &self,
+        release_kind: ObjectKind,
+        version: &str,
+        revert: bool,
+    ) -> Result<()> {
+        let tagged = self.tagged_versions()?;
+        let index = tagged
+            .iter()
+            .position(|t| t.tag == version || t.version.to_string() == version)
+            .ok_or_else(|| format_err!("No release tag found for version {version}"))?;
+        let release = &tagged[index];
+        if release.yanked {
+            bail!("{} was already yanked ({})", release.version, release.tag);
+        }
+        let tag = &release.tag;
+        let yanked_tag = format!("{YANKED_TAG_PREFIX}{tag}");
+        self.check_signing()?;
+
+        if can_ask_questions()
+            && console::user_attended_stderr()
+            && !Confirm::with_theme(get_color_theme().as_ref())
+                .with_prompt(format!(
+                    "Yank {tag}? This deletes the tag locally and on the remote{}",
+                    if revert {
+                        ", and reverts its changes"
+                    } else {
+                        ""
+                    }
+                ))
+                .default(false)
+                .interact()?
+        {
+            bail!(PargitError::UserAborted);
+        }
+
+        let version = release.version.to_string();
+        let release_name = self.config.get_release_name(&version);
+        let commit = self.repo.resolve_commit(tag)?;
+        self.record_release(&release_name, &version, tag);
+        if revert {
+            let previous = tagged[..index].iter().rfind(|t| !t.yanked);
+            self.revert_release(tag, previous.map(|t| t.tag.as_str()))?;
+        }
+
+        info!("Replacing tag {tag} with {yanked_tag}");
+        self.repo.create_tag_at(
+            &yanked_tag,
+            &commit,
+            &format!("Yank {tag}"),
+            self.config.sign_tags,
+        )?;
+        self.repo.delete_tag(tag)?;
+        if self.repo.remote_url("origin").is_some() {
+            let mut refspecs = vec![format!("refs/tags/{yanked_tag}")];
+            if self.repo.find_remote_tag("origin", tag)?.is_some() {
+                refspecs.push(format!(":refs/tags/{tag}"));
+            }
+            info!("Pushing {yanked_tag} and deleting {tag} from remote");
+            self.repo_path
+                .shell(format!("git push origin {}", refspecs.join(" ")))?;
+            self.record_push(format!("refs/tags/{yanked_tag}"));
+        } else {
+            warn!("No origin remote configured. Not pushing {yanked_tag}");
+        }
+        if !self.config.alias_tags.is_empty() {
+            warn!("Alias tags pointing at {tag} were not moved. Move them to the previous release if needed");
+        }
+
+        self.notify_webhooks(
+            "yank",
+            release_kind,
+            &release_name,
+            commit,
+            Some(&version),
+            Some(tag),
+        );
+        Ok(())
+    }
+
+    /// Reverts the changes a release brought into the main and develop branches, and pushes them. Branches the release
+    /// was merged into are reverted to before the merge, and fast-forwarded branches to the previous release
+    fn revert_release(&self, tag: &str, previous_tag: Option<&str>) -> Result<()> {
+        let current_branch_name = self.repo.current_branch_name()?;
+        let mut undo = ExitStack::default();
+        let current_branch_name_clone = current_branch_name.clone();
+        undo.remember("Switching back", move || {
+            self.repo
+                .switch_to_branch_name(&current_branch_name_clone)
+                .ignore_errors()
+        });
+        let mut reverted = Vec::new();
+        for branch in [
+            &self.config.main_branch_name,
+            &self.config.develop_branch_name,
+        ] {
+            let (base, tip) = match self.repo.find_merge_of(branch, tag)? {
+                Some(merge) => (format!("{merge}^1"), merge),
+                None if !self.repo.contains(branch, tag)? => {
+                    warn!("{branch} does not contain {tag}. Not reverting it");
+                    continue;
+                }
+                None if self.repo.is_merge_commit(tag)? => (format!("{tag}^1"), tag.to_owned()),
+                None => match previous_tag {
+                    Some(previous_tag) => (previous_tag.to_owned(), tag.to_owned()),
+                    None => bail!(
+                        "Cannot revert {tag} on {branch}, which was fast-forwarded to the first release"
+                    ),
+                },
+            };
+            self.repo.switch_to_branch_name(branch)?;
+            let head = self.repo.resolve_commit("HEAD")?;
+            undo.remember(format!("Resetting {branch}"), move || {
+                // discards a conflicting revert first, which would prevent switching branches
+                self.repo
+                    .reset_hard("HEAD")
+                    .and_then(|_| self.repo.switch_to_branch_name(branch))
+                    .and_then(|_| self.repo.reset_hard(&head))
+                    .ignore_errors()
+            });
+            info!("Reverting {tag} on {branch}");
+            if self.repo.revert_changes(
+                &base,
+                &tip,
+                &format!("Revert {tag}"),
+                self.config.sign_commits,
+            )? {
+                reverted.push(branch.as_str());
+            } else {
+                info!("{branch} has no changes from {tag} to revert");
+            }
+        }
+
+        if !reverted.is_empty() {
+            info!("Pushing {}", reverted.join(", "));
+            self.repo_path
+                .shell(format!("git push origin {}", reverted.join(" ")))
+                .context(PargitError::PushRejected("reverted branches".into()))?;
+            for branch in &reverted {
+                self.record_push(format!("refs/heads/{branch}"));
+            }
+        }
+        undo.forget();
+        self.repo.switch_to_branch_name(&current_branch_name)
+    }
+
+    /// Prints the metadata recorded when releasing a version (or tag)
+    pub fn release_info(&self, version: &str, quiet: bool) -> Result<()> {
+        let tagged = self.tagged_versions()?;
+        let release = tagged
+            .iter()
+            .find(|t| t.tag == version || t.version.to_string() == version)
+            .ok_or_else(|| format_err!("No release tag found for version {version}"))?;
+        let fetched = self.repo.remote_url("origin").is_some()
+            && self
+                .repo
+                .fetch_notes("origin", NOTES_REF)
+                .map_err(|e| warn!("Failed fetching release metadata from remote: {e:?}"))
+                .is_ok();
+        let note = self
+            .repo
+            .read_note(NOTES_REF, &release.tag)?
+            .ok_or_else(|| format_err!("No release metadata recorded for {}", release.tag))?;
+        if fetched
+            && self
+                .repo
+                .read_note(&remote_notes_ref("origin", NOTES_REF), &release.tag)?
+                .is_none()
+        {
+            warn!(
+                "Release metadata of {} was never pushed, so other clones don't have it. Push it with `git push origin {NOTES_REF}`",
+                release.tag
+            );
+        }
+        let metadata = toml::from_str::<ReleaseMetadata>(&note)
+            .with_context(|| format!("Invalid release metadata recorded for {}", release.tag))?;
+        if !quiet {
+            println!("{metadata}");
+            if release.yanked {
+                println!("Yanked:         yes ({})", release.tag);
+            }
+        }
+        output::record(|report| report.metadata = Some(metadata));
+        Ok(())
+    }
+
+    /// Attaches the release's metadata as a note to its tagged commit, and pushes it
+    fn record_metadata(
+        &self,
+        release_kind: ObjectKind,
+        release_name: &str,
+        version: &str,
+        tag: &str,
+        checks: Vec<String>,
+    ) -> Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let metadata = ReleaseMetadata {
+            name: release_name.to_owned(),
+            kind: release_kind.to_string(),
+            version: version.to_owned(),
+            tag: tag.to_owned(),
+            released_by: self.repo.user_identity(),
+            released_at: format_utc_timestamp(now),
+            host: self
+                .repo_path
+                .shell_output("hostname")
+                .ok()
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+                .filter(|host| !host.is_empty()),
+            pargit_version: env!("CARGO_PKG_VERSION").to_owned(),
+            checks,
+        };
+        // notes of releases made elsewhere may have been pushed since
+        self.repo.fetch_notes("origin", NOTES_REF)?;
         self.repo
-            .merge_branch_name(&temp_branch_name, "Merge temporary release branch")?;
-        info!("Pushing tags");
-        self.repo_path.shell("git push --tags")?;
+            .set_note(NOTES_REF, tag, &toml::to_string(&metadata)?)?;
+        info!("Pushing release metadata");
+        self.repo_path
+            .shell(format!("git push origin {NOTES_REF}"))
+            .with_context(|| {
+                format!("Release metadata was recorded locally but not pushed, so other clones don't have it. Push it with `git push origin {NOTES_REF}`")
+            })?;
+        self.record_push(NOTES_REF);
+        Ok(())
+    }
+
+    /// Merges a pushed release back to the develop branch, deletes its branches and moves alias tags
+    fn merge_back_release(
+        &self,
+        release_kind: ObjectKind,
+        release_name: String,
+        temp_branch_name: &str,
+        tag: &str,
+        message: MessageContext,
+        checks: Vec<String>,
+    ) -> Result<()> {
         self.repo
             .switch_to_branch_name(&self.config.develop_branch_name)?;
         info!("Merging to develop branch");
+        let message = MessageContext {
+            branch: self.config.main_branch_name.clone(),
+            commit_count: self.repo.count_commits_between(
+                &self.config.develop_branch_name,
+                &self.config.main_branch_name,
+            )?,
+            ..message
+        };
         self.repo.merge_branch_name(
             &self.config.main_branch_name,
-            &format!("Merge {} branch", self.config.main_branch_name),
+            &message.render(&self.config.messages.develop_merge)?,
+            self.config.sign_commits,
         )?;
 
         self.repo
             .find_branch(temp_branch_name)?
             .delete()
             .context("Failed deleting temporary branch")?;
-        self.pargit_delete(release_kind, Some(release_name))?;
+        self.delete_branch(release_kind, Some(release_name.clone()))?;
         info!("Pushing develop branch");
         self.repo_path.shell(format!(
             "git push origin {0}:{0}",
             self.config.develop_branch_name
-        ))
+        ))?;
+        self.record_push(format!("refs/heads/{}", self.config.develop_branch_name));
+
+        // like the steps following it, recording metadata can't fail the already pushed release
+        if let Err(e) =
+            self.record_metadata(release_kind, &release_name, &message.version, tag, checks)
+        {
+            warn!("Failed recording release metadata for {tag}: {e:?}");
+        }
+
+        if let Some(forge) = &self.config.forge {
+            info!("Creating {} release for {tag}", forge.provider);
+            // the release was already pushed at this point, so there's nothing to roll back
+            if let Err(e) =
+                crate::forge::publish_release(forge, &self.project_path, tag, &message.version)
+            {
+                warn!(
+                    "Failed creating {} release for {tag}: {e:?}",
+                    forge.provider
+                );
+            }
+        }
+
+        self.notify_webhooks(
+            "finish",
+            release_kind,
+            &release_name,
+            self.repo.resolve_commit(tag)?,
+            Some(&message.version),
+            Some(tag),
+        );
+
+        if !self.config.alias_tags.is_empty() {
+            if let Err(e) = self.update_alias_tags(tag, &message.version) {
+                warn!("Failed updating alias tags for {tag}: {e:?}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the configured alias tags (e.g. v1, v1.4) to point at the released tag, unless a newer release on the
+    /// same line already exists
+    fn update_alias_tags(&self, tag: &str, version: &str) -> Result<()> {
+        let version = match Version::parse(version) {
+            Ok(version) => version,
+            Err(_) => {
+                warn!("Release {version} is not a semantic version. Not updating alias tags");
+                return Ok(());
+            }
+        };
+        if version.is_prerelease() {
+            info!("Not updating alias tags for pre-release {version}");
+            return Ok(());
+        }
+        let prefix = tag.strip_suffix(&version.to_string()).unwrap_or_default();
+        let tagged = self.tagged_versions()?;
+
+        let mut aliases = Vec::new();
+        for alias in &self.config.alias_tags {
+            let (alias_name, same_line): (_, &dyn Fn(&Version) -> bool) = match alias {
+                AliasTag::Major => (format!("{prefix}{}", version.major), &|v: &Version| {
+                    v.major == version.major
+                }),
+                AliasTag::Minor => (
+                    format!("{prefix}{}.{}", version.major, version.minor),
+                    &|v: &Version| v.major == version.major && v.minor == version.minor,
+                ),
+            };
+            if let Some(newer) = tagged.iter().find(|t| {
+                !t.yanked
+                    && !t.version.is_prerelease()
+                    && same_line(&t.version)
+                    && t.version > version
+            }) {
+                warn!(
+                    "Not moving alias tag {alias_name} backwards, since {} is newer than {version}",
+                    newer.tag
+                );
+                continue;
+            }
+            info!("Moving alias tag {alias_name} to {tag}");
+            self.repo.force_lightweight_tag(&alias_name, tag)?;
+            aliases.push(format!("+refs/tags/{alias_name}"));
+        }
+
+        if !aliases.is_empty() {
+            info!("Pushing alias tags");
+            self.repo_path
+                .shell(format!("git push origin {}", aliases.join(" ")))?;
+            for alias in aliases {
+                self.record_push(alias.trim_start_matches('+'));
+            }
+        }
+        Ok(())
     }
 
     fn resolve_name(&self, kind: ObjectKind, name: Option<impl Into<String>>) -> Result<String> {
@@ -373,28 +1027,79 @@ impl Pargit {
     }
 
     fn compile(&self) -> Result<()> {
-        if let Some(type_) = &self.type_ {
-            match type_ {
-                ProjectType::Rust => {
-                    let compilation_command = self
-                        .config
-                        .project_config
-                        .compilation_command
-                        .as_deref()
-                        .unwrap_or("cargo check --workspace --tests");
-                    // info!("Compiling project (cargo check)...");
-                    self.project_path
-                        .shell(compilation_command)
-                        .context("Failed building project")
-                }
-            }
+        if let Some(compilation_command) = self.compilation_command() {
+            // info!("Compiling project (cargo check)...");
+            self.project_path
+                .shell(compilation_command)
+                .context("Failed building project")
         } else {
             Ok(())
         }
     }
 
-    fn resolve_release(&self, version_spec: VersionSpec) -> Result<Release> {
-        let version_files = self.get_version_files_to_bump()?;
+    /// The command building the project before releasing it, if it needs building
+    pub(crate) fn compilation_command(&self) -> Option<&str> {
+        self.type_.map(|type_| match type_ {
+            ProjectType::Rust => self
+                .config
+                .project_config
+                .compilation_command
+                .as_deref()
+                .unwrap_or("cargo check --workspace --tests"),
+        })
+    }
+
+    fn message_context(
+        &self,
+        kind: ObjectKind,
+        name: &str,
+        branch: &str,
+        base: &str,
+        version: &str,
+        tag: &str,
+    ) -> Result<MessageContext> {
+        Ok(MessageContext {
+            kind,
+            name: name.to_owned(),
+            branch: branch.to_owned(),
+            version: version.to_owned(),
+            previous_version: self
+                .try_get_latest_tagged_version()?
+                .map(|(version, _)| version.to_string())
+                .unwrap_or_default(),
+            tag: tag.to_owned(),
+            commit_count: self.repo.count_commits_between(base, branch)?,
+        })
+    }
+
+    fn lock_versions(&self, bumped_files: &[VersionFile]) -> Result<()> {
+        match self.type_ {
+            Some(ProjectType::Rust) => match self.config.project_config.lock_refresh {
+                LockRefresh::Compile => {
+                    info!("Compiling project to lock new version");
+                    self.compile()
+                }
+                LockRefresh::Rewrite => {
+                    info!("Rewriting Cargo.lock to lock new version");
+                    crate::project_types::rust::rewrite_cargo_lock(&self.project_path, bumped_files)
+                }
+                LockRefresh::CargoUpdate => {
+                    info!("Updating Cargo.lock to lock new version");
+                    self.project_path
+                        .shell("cargo update --workspace --offline")
+                        .context("Failed updating Cargo.lock")
+                }
+            },
+            None => Ok(()),
+        }
+    }
+
+    fn resolve_release(
+        &self,
+        version_spec: VersionSpec,
+        selection: &VersionFileSelection,
+    ) -> Result<Release> {
+        let version_files = self.get_version_files_to_bump(selection)?;
         let (new_version, prefix) = match version_spec {
             VersionSpec::Exact(version) => (version, None),
             VersionSpec::Bump(bump_kind) => {
@@ -404,7 +1109,17 @@ impl Pargit {
                     (version_files[0].version(), None)
                 };
 
-                (next_version(&current_version, bump_kind), prefix)
+                let yanked = self
+                    .tagged_versions()?
+                    .into_iter()
+                    .filter(|tagged| tagged.yanked)
+                    .map(|tagged| tagged.version)
+                    .collect::<Vec<_>>();
+                let mut new_version = next_version(&current_version, bump_kind);
+                while yanked.contains(&new_version) {
+                    new_version = next_version(&new_version, bump_kind);
+                }
+                (new_version, prefix)
             }
         };
 
@@ -417,29 +1132,129 @@ impl Pargit {
     }
 
     fn try_get_latest_tagged_version(&self) -> Result<Option<(Version, String)>> {
+        Ok(self
+            .tagged_versions()?
+            .into_iter()
+            .rfind(|tagged| !tagged.yanked)
+            .map(|tagged| (tagged.version, tagged.prefix)))
+    }
+
+    /// Returns the versions of all release tags of the project (including yanked ones), sorted by version
+    pub(crate) fn tagged_versions(&self) -> Result<Vec<TaggedVersion>> {
         let tags = self.repo.tags()?;
 
         let mut versions = Vec::new();
+        let namespace = self.config.release_namespace();
+        // the configured prefix comes first, then the conventional ones for tags made before it was set
+        let mut prefixes = vec![self.config.tag_prefix.clone()];
+        for prefix in [format!("{namespace}v"), namespace] {
+            if !prefixes.contains(&prefix) {
+                prefixes.push(prefix);
+            }
+        }
 
-        for tag in tags {
-            for prefix in &["v", ""] {
-                if let Some(v) = tag.strip_prefix(prefix) {
+        for tag in &tags {
+            let (name, yanked) = match tag.strip_prefix(YANKED_TAG_PREFIX) {
+                Some(name) => (name, true),
+                // a yanked release's tag may have been fetched again from a clone that still has it
+                None if tags.contains(&format!("{YANKED_TAG_PREFIX}{tag}")) => continue,
+                None => (tag.as_str(), false),
+            };
+            for prefix in &prefixes {
+                if let Some(v) = name.strip_prefix(prefix.as_str()) {
                     if let Ok(v) = Version::parse(v) {
-                        versions.push((v, (*prefix).to_owned()));
+                        versions.push(TaggedVersion {
+                            version: v,
+                            prefix: prefix.clone(),
+                            tag: tag.clone(),
+                            yanked,
+                        });
                         break;
                     }
                 }
             }
         }
 
-        versions.sort_by_key(|(version, _)| version.clone());
+        versions.sort_by_key(|tagged| tagged.version.clone());
 
-        Ok(versions.into_iter().next_back())
+        Ok(versions)
     }
 
-    fn get_version_files_to_bump(&self) -> Result<Vec<VersionFile>> {
+    /// Makes sure the released version moves forward relative to existing tags, and that the project's version files
+    /// agree with the latest tag. For hotfixes, only tags on the release's own major.minor line are considered,
+    /// allowing fixes to older support lines
+    fn check_release_version(&self, release: &Release, kind: ObjectKind) -> Result<()> {
+        let tagged: Vec<_> = self
+            .tagged_versions()?
+            .into_iter()
+            .filter(|t| {
+                kind != ObjectKind::Hotfix
+                    || (t.version.major, t.version.minor)
+                        == (release.version.major, release.version.minor)
+            })
+            .collect();
+        if let Some(yanked) = tagged
+            .iter()
+            .find(|t| t.yanked && t.version == release.version)
+        {
+            bail!(PargitError::CheckFailed(format!(
+                "Version {} was yanked ({}), and cannot be released again",
+                release.version, yanked.tag
+            )));
+        }
+        let latest = match tagged.iter().rfind(|t| !t.yanked) {
+            Some(latest) => latest,
+            None => return Ok(()),
+        };
+
+        if release.version <= latest.version {
+            bail!(PargitError::CheckFailed(format!(
+                "Version {} is not greater than existing tag {}. Use --force to release it anyway",
+                release.version, latest.tag
+            )));
+        }
+
+        for version_file in release.version_files.iter().flatten() {
+            let version = version_file.version();
+            if !agrees_with_tags(&version, &tagged) {
+                bail!(PargitError::CheckFailed(format!(
+                    "{:?} has version {version}, which does not match the latest tag {}. Perhaps a merge went wrong? Use --force to release anyway",
+                    version_file.path(),
+                    latest.tag
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_version_files_to_bump(
+        &self,
+        selection: &VersionFileSelection,
+    ) -> Result<Vec<VersionFile>> {
         let version_files = self.get_all_version_files()?;
 
+        if selection.all_version_files {
+            return Ok(version_files);
+        }
+
+        if let Some(package) = &selection.package {
+            return self.select_version_file(version_files, |f| f.name() == Some(package.as_str()));
+        }
+
+        if let Some(path) = &selection.version_file {
+            let path = self
+                .project_path
+                .join(path)
+                .canonicalize()
+                .with_context(|| format!("Version file {path:?} not found"))?;
+            // a directory selects the version file directly inside it
+            return self.select_version_file(version_files, |f| {
+                f.path()
+                    .canonicalize()
+                    .is_ok_and(|file| file == path || file.parent() == Some(&path))
+            });
+        }
+
         // if we have a single version - we should bump them all
         if version_files
             .iter()
@@ -454,13 +1269,21 @@ impl Pargit {
         let index = if version_files.len() > 1 {
             let selections = version_files
                 .iter()
-                .map(|version_file| {
-                    let relpath =
-                        pathdiff::diff_paths(version_file.path(), &self.project_path).unwrap();
-                    relpath.to_string_lossy().to_string()
-                })
+                .map(|version_file| self.describe_version_file(version_file))
                 .collect::<Vec<_>>();
 
+            if !can_ask_questions() || !console::user_attended_stderr() {
+                bail!(
+                    "Multiple {} files with different versions found. Use --package, --version-file or --all-version-files to select which ones to bump. Candidates:\n{}",
+                    version_files[0].typename(),
+                    selections
+                        .iter()
+                        .map(|s| format!("  {s}"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                );
+            }
+
             Select::with_theme(&ColorfulTheme::default())
                 .with_prompt(format!(
                     "Multiple {} files found. Which one would you like to bump?",
@@ -477,22 +1300,114 @@ impl Pargit {
         Ok(version_files.into_iter().nth(index).into_iter().collect())
     }
 
-    fn get_all_version_files(&self) -> Result<Vec<VersionFile>> {
+    fn select_version_file(
+        &self,
+        version_files: Vec<VersionFile>,
+        predicate: impl Fn(&VersionFile) -> bool,
+    ) -> Result<Vec<VersionFile>> {
+        let candidates = version_files
+            .iter()
+            .map(|f| format!("  {}", self.describe_version_file(f)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let selected: Vec<_> = version_files.into_iter().filter(predicate).collect();
+        if selected.is_empty() {
+            bail!("Selected version file not found. Candidates:\n{candidates}");
+        }
+        Ok(selected)
+    }
+
+    fn describe_version_file(&self, version_file: &VersionFile) -> String {
+        let relpath = pathdiff::diff_paths(version_file.path(), &self.project_path).unwrap();
+        match version_file.name() {
+            Some(name) => format!(
+                "{} ({name}, version {})",
+                relpath.to_string_lossy(),
+                version_file.version()
+            ),
+            None => format!(
+                "{} (version {})",
+                relpath.to_string_lossy(),
+                version_file.version()
+            ),
+        }
+    }
+
+    pub(crate) fn get_all_version_files(&self) -> Result<Vec<VersionFile>> {
         self.type_
             .map(|type_| match type_ {
-                ProjectType::Rust => crate::project_types::rust::find_cargo_tomls(&self.repo),
+                ProjectType::Rust => crate::project_types::rust::find_cargo_tomls(
+                    &self.repo,
+                    // with several projects in the repository, each one only owns its own subpath
+                    if self.config.project_name.is_some() {
+                        &self.project_path
+                    } else {
+                        &self.repo_path
+                    },
+                    &self.project_path,
+                    self.config.project_config.discovery,
+                ),
             })
             .unwrap_or_else(|| Ok(Vec::new()))
     }
 
     // Checks
-    fn check_pre_release(&self, options: &ReleaseOptions) -> Result<()> {
+    fn check_feature_name(&self, name: &str) -> Result<()> {
+        if let Some(pattern) = &self.config.feature_name_pattern {
+            let regex = regex::Regex::new(pattern).map_err(|e| {
+                PargitError::ConfigInvalid(format!("Invalid feature_name_pattern {pattern:?}: {e}"))
+            })?;
+            if !regex.is_match(name) {
+                bail!("Feature name {name:?} does not match the required pattern {pattern:?} (feature_name_pattern)");
+            }
+        }
+        Ok(())
+    }
+
+    fn check_remote_tag(&self, tag: &str) -> Result<()> {
+        debug!("Checking whether tag {tag} exists on remote");
+        match self.repo.find_remote_tag("origin", tag) {
+            Ok(Some(description)) => bail!(PargitError::TagExists(format!(
+                "Tag {tag} already exists on remote origin ({description}). Fetch tags from the remote and choose a different version"
+            ))),
+            Ok(None) => Ok(()),
+            Err(e) => {
+                warn!("Could not check whether tag {tag} exists on remote: {e}");
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn check_signing(&self) -> Result<()> {
+        if self.config.sign_tags || self.config.sign_commits {
+            debug!("Checking signing key availability");
+            self.repo
+                .check_signing_key()
+                .context(PargitError::CheckFailed(
+                    "Signing is enabled, but no signing key is available".into(),
+                ))?;
+        }
+        Ok(())
+    }
+
+    /// Runs the checks a release must pass before finishing it, returning the names of the checks that ran
+    fn check_pre_release(&self, options: &ReleaseOptions) -> Result<Vec<String>> {
         info!("Running pre-release checks...");
+        let mut checks = Vec::new();
+        if self.config.sign_tags || self.config.sign_commits {
+            // verified by check_signing before finishing
+            checks.push("signing_key".to_owned());
+        }
 
-        self.compile()?;
+        self.compile()
+            .context(PargitError::CheckFailed("Pre-release checks failed".into()))?;
+        if self.compilation_command().is_some() {
+            checks.push("compilation".to_owned());
+        }
         if self.repo.is_dirty()? {
-            bail!("Repository became dirty after build attempt. Perhaps Cargo.lock was not a part of the last commit?");
+            bail!(PargitError::CheckFailed("Repository became dirty after build attempt. Perhaps Cargo.lock was not a part of the last commit?".into()));
         }
+        checks.push("clean_after_compilation".to_owned());
 
         for branch_name in &[
             &self.config.develop_branch_name,
@@ -504,21 +1419,129 @@ impl Pargit {
                     self.repo.pull_branch_from_remote(branch_name, true)?;
                     assert!(self.repo.is_branch_up_to_date(branch_name)?);
                 } else {
-                    bail!("Local {0} branch is behind remote {0} branch. Update your local {0} branch before creating a release.", branch_name);
+                    bail!(PargitError::BehindRemote(branch_name.to_string()));
                 }
             }
+            checks.push(format!("{branch_name}_up_to_date"));
         }
 
-        Ok(())
+        Ok(checks)
+    }
+
+    fn record_release(&self, name: &str, version: &str, tag: &str) {
+        let release = ReleaseReport {
+            name: name.to_owned(),
+            version: version.to_owned(),
+            tag: tag.to_owned(),
+        };
+        output::record(|report| report.release = Some(release));
+    }
+
+    fn record_push(&self, refname: impl Into<String>) {
+        let refname = refname.into();
+        output::record(|report| report.pushed_refs.push(refname));
+    }
+
+    fn record_bumped_files(&self, files: &[VersionFile]) {
+        output::record(|report| {
+            report.bumped_files.extend(files.iter().map(|file| {
+                file.path()
+                    .strip_prefix(&self.repo_path)
+                    .unwrap_or(file.path())
+                    .to_owned()
+            }))
+        });
+    }
+
+    fn notify_webhooks(
+        &self,
+        action: &'static str,
+        kind: ObjectKind,
+        name: &str,
+        commit: String,
+        version: Option<&str>,
+        tag: Option<&str>,
+    ) {
+        if self.config.webhooks.is_empty() {
+            return;
+        }
+        let payload = WebhookPayload {
+            event: format!("{kind}_{action}"),
+            action,
+            kind: kind.to_string(),
+            name: name.to_owned(),
+            project: self.config.project_name.clone(),
+            version: version.map(String::from),
+            tag: tag.map(String::from),
+            commit,
+            remote: self.repo.remote_url("origin"),
+            user: self.repo.user_identity(),
+        };
+        crate::webhooks::notify(&self.config.webhooks, &payload);
     }
 
     /// Get a reference to the project's config.
     pub fn config(&self) -> &Config {
         &self.config
     }
+
+    pub(crate) fn repo(&self) -> &Repository {
+        &self.repo
+    }
+
+    pub(crate) fn project_path(&self) -> &Path {
+        &self.project_path
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
 enum ProjectType {
     Rust,
 }
+
+pub(crate) struct TaggedVersion {
+    pub version: Version,
+    pub prefix: String,
+    pub tag: String,
+    /// Whether the release was withdrawn, and its tag replaced by a yanked/ tag
+    pub yanked: bool,
+}
+
+/// Whether a version file's version is consistent with the release tags: equal to the latest release, to a release
+/// yanked since then (without reverting its merge), or a pre-release of a newer version
+pub(crate) fn agrees_with_tags(version: &Version, tagged: &[TaggedVersion]) -> bool {
+    let latest = match tagged.iter().rposition(|t| !t.yanked) {
+        Some(latest) => latest,
+        None => return true,
+    };
+    tagged[latest..].iter().any(|t| t.version == *version)
+        || (*version > tagged[latest].version && version.is_prerelease())
+}
+
+/// Values available to commit and tag message templates
+struct MessageContext {
+    kind: ObjectKind,
+    name: String,
+    branch: String,
+    version: String,
+    previous_version: String,
+    tag: String,
+    commit_count: usize,
+}
+
+impl MessageContext {
+    fn render(&self, template: &str) -> Result<String> {
+        render_template(
+            template,
+            &[
+                ("kind", &self.kind.to_string()),
+                ("name", &self.name),
+                ("branch", &self.branch),
+                ("version", &self.version),
+                ("previous_version", &self.previous_version),
+                ("tag", &self.tag),
+                ("commit_count", &self.commit_count.to_string()),
+            ],
+        )
+    }
+}
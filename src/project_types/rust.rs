@@ -1,12 +1,43 @@
 use anyhow::{Context, Result};
 use log::debug;
 use semver::Version;
-use std::{collections::HashSet, fs::read_to_string, io::Write, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::read_to_string,
+    io::Write,
+    path::{Path, PathBuf},
+};
 use toml_edit::value;
 
-use crate::{repo::Repository, version_file::VersionFile};
+use crate::{config::DiscoveryMode, repo::Repository, version_file::VersionFile};
 
-pub fn find_cargo_tomls(repo: &Repository) -> Result<Vec<VersionFile>> {
+pub fn find_cargo_tomls(
+    repo: &Repository,
+    search_root: &Path,
+    project_path: &Path,
+    discovery: DiscoveryMode,
+) -> Result<Vec<VersionFile>> {
+    let paths = match discovery {
+        DiscoveryMode::Walk => walk_cargo_tomls(repo, search_root)?,
+        DiscoveryMode::Index => indexed_cargo_tomls(repo, search_root)?,
+        DiscoveryMode::Workspace => workspace_cargo_tomls(project_path)?,
+    };
+
+    let mut returned = Vec::new();
+    for path in paths {
+        if let Some(version_file) = load_cargo_toml(&path)? {
+            returned.push(version_file);
+        }
+    }
+
+    if returned.is_empty() {
+        anyhow::bail!("Could not find Cargo.toml files in {:?}", search_root);
+    }
+
+    Ok(returned)
+}
+
+fn walk_cargo_tomls(repo: &Repository, search_root: &Path) -> Result<Vec<PathBuf>> {
     let submodule_paths: HashSet<_> = repo
         .submodule_paths()?
         .into_iter()
@@ -16,7 +47,7 @@ pub fn find_cargo_tomls(repo: &Repository) -> Result<Vec<VersionFile>> {
     let repo_path = repo.path().to_path_buf();
 
     let mut returned = Vec::new();
-    for entry in walkdir::WalkDir::new(repo.path())
+    for entry in walkdir::WalkDir::new(search_root)
         .contents_first(false)
         .into_iter()
         .filter_entry(|entry| {
@@ -45,47 +76,175 @@ pub fn find_cargo_tomls(repo: &Repository) -> Result<Vec<VersionFile>> {
                 debug!("{:?} is ignored in .gitignore. Skipping", path);
                 continue;
             }
+            returned.push(path.to_owned());
+        }
+    }
+    Ok(returned)
+}
 
-            let toml: toml_edit::DocumentMut = read_to_string(path)
-                .with_context(|| format!("Failed reading file {path:?} "))?
-                .parse()
-                .with_context(|| format!("Failed parsing {path:?}"))?;
+fn indexed_cargo_tomls(repo: &Repository, search_root: &Path) -> Result<Vec<PathBuf>> {
+    Ok(repo
+        .tracked_paths()?
+        .into_iter()
+        .filter(|path| path.file_name().map(|s| s == "Cargo.toml").unwrap_or(false))
+        .map(|path| repo.path().join(path))
+        .filter(|path| path.starts_with(search_root) && path.exists())
+        .collect())
+}
 
-            if let Some(version) = toml
-                .get("package")
-                .and_then(|t| t.get("version")?.as_str())
-                .map(Version::parse)
-                .transpose()
-                .with_context(|| format!("Failed parsing version for {path:?}"))?
-            {
-                debug!("Found Cargo.toml: {path:?} (version={version})");
-                returned.push(VersionFile::CargoToml {
-                    path: path.to_owned(),
-                    version,
-                    is_workspace: false,
-                })
-            } else if let Some(version) = toml
-                .get("workspace")
-                .and_then(|w| w.get("package")?.get("version")?.as_str())
-                .map(Version::parse)
-                .transpose()
-                .with_context(|| format!("Failed parsing workspace package version for {path:?}"))?
+fn workspace_cargo_tomls(project_path: &Path) -> Result<Vec<PathBuf>> {
+    let root = project_path.join("Cargo.toml");
+    let toml = read_manifest(&root)?;
+
+    let globs = |key: &str| -> Vec<String> {
+        toml.get("workspace")
+            .and_then(|w| w.get(key)?.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let expand = |patterns: Vec<String>| -> Result<Vec<PathBuf>> {
+        let mut returned = Vec::new();
+        for pattern in patterns {
+            let pattern = project_path.join(pattern);
+            for path in glob::glob(&pattern.to_string_lossy())
+                .with_context(|| format!("Invalid workspace glob {pattern:?}"))?
             {
-                debug!("Found workspace Cargo.toml: {path:?} (version={version})",);
-                returned.push(VersionFile::CargoToml {
-                    path: path.to_owned(),
-                    version,
-                    is_workspace: true,
-                })
+                returned.push(path?.join("Cargo.toml"));
             }
         }
+        Ok(returned)
+    };
+
+    let excluded: HashSet<_> = expand(globs("exclude"))?.into_iter().collect();
+
+    let mut returned = vec![root];
+    for path in expand(globs("members"))? {
+        if excluded.contains(&path) {
+            debug!("{:?} is excluded from the workspace. Skipping", path);
+        } else if path.exists() {
+            returned.push(path);
+        }
     }
+    Ok(returned)
+}
 
-    if returned.is_empty() {
-        anyhow::bail!("Could not find Cargo.toml files in {:?}", repo.path());
+fn load_cargo_toml(path: &Path) -> Result<Option<VersionFile>> {
+    let toml = read_manifest(path)?;
+
+    if let Some(version) = toml
+        .get("package")
+        .and_then(|t| t.get("version")?.as_str())
+        .map(Version::parse)
+        .transpose()
+        .with_context(|| format!("Failed parsing version for {path:?}"))?
+    {
+        debug!("Found Cargo.toml: {path:?} (version={version})");
+        Ok(Some(VersionFile::CargoToml {
+            path: path.to_owned(),
+            name: toml
+                .get("package")
+                .and_then(|t| t.get("name")?.as_str())
+                .map(String::from),
+            version,
+            is_workspace: false,
+        }))
+    } else if let Some(version) = toml
+        .get("workspace")
+        .and_then(|w| w.get("package")?.get("version")?.as_str())
+        .map(Version::parse)
+        .transpose()
+        .with_context(|| format!("Failed parsing workspace package version for {path:?}"))?
+    {
+        debug!("Found workspace Cargo.toml: {path:?} (version={version})",);
+        Ok(Some(VersionFile::CargoToml {
+            path: path.to_owned(),
+            name: None,
+            version,
+            is_workspace: true,
+        }))
+    } else {
+        Ok(None)
     }
+}
 
-    Ok(returned)
+/// Updates the versions of the packages defined by the given (already bumped) manifests in Cargo.lock, without
+/// resolving or compiling anything
+pub fn rewrite_cargo_lock(project_path: &Path, bumped_files: &[VersionFile]) -> Result<()> {
+    let lock_path = project_path.join("Cargo.lock");
+    if !lock_path.exists() {
+        debug!("{lock_path:?} does not exist. Skipping");
+        return Ok(());
+    }
+
+    let mut versions = HashMap::new();
+    for bumped_file in bumped_files {
+        let path = bumped_file.path();
+        let toml = read_manifest(path)?;
+        if let Some(version) = toml
+            .get("workspace")
+            .and_then(|w| w.get("package")?.get("version")?.as_str())
+        {
+            let workspace_root = path.parent().unwrap_or(project_path);
+            // the root manifest comes first, and its own package may inherit the version too
+            for member_path in workspace_cargo_tomls(workspace_root)? {
+                let member = read_manifest(&member_path)?;
+                let inherits = member
+                    .get("package")
+                    .and_then(|p| p.get("version")?.get("workspace")?.as_bool())
+                    .unwrap_or(false);
+                if let Some(name) = member.get("package").and_then(|p| p.get("name")?.as_str()) {
+                    if inherits {
+                        versions.insert(name.to_owned(), version.to_owned());
+                    }
+                }
+            }
+        }
+        if let (Some(name), Some(version)) = (
+            toml.get("package").and_then(|p| p.get("name")?.as_str()),
+            toml.get("package").and_then(|p| p.get("version")?.as_str()),
+        ) {
+            versions.insert(name.to_owned(), version.to_owned());
+        }
+    }
+
+    let mut lock = read_manifest(&lock_path)?;
+    if let Some(packages) = lock
+        .get_mut("package")
+        .and_then(|p| p.as_array_of_tables_mut())
+    {
+        for package in packages.iter_mut() {
+            // packages with a source come from registries or git, and never belong to the workspace
+            if package.contains_key("source") {
+                continue;
+            }
+            if let Some(version) = package
+                .get("name")
+                .and_then(|n| n.as_str())
+                .and_then(|name| versions.get(name))
+            {
+                debug!(
+                    "Locking {:?} to version {version}",
+                    package["name"].as_str()
+                );
+                package["version"] = value(version.as_str());
+            }
+        }
+    }
+
+    std::fs::write(&lock_path, lock.to_string())
+        .with_context(|| format!("Failed writing {lock_path:?}"))
+}
+
+fn read_manifest(path: &Path) -> Result<toml_edit::DocumentMut> {
+    read_to_string(path)
+        .with_context(|| format!("Failed reading file {path:?}"))?
+        .parse()
+        .with_context(|| format!("Failed parsing {path:?}"))
 }
 
 pub fn write_cargo_toml_version(
@@ -17,7 +17,7 @@ impl Release {
         prefix: Option<String>,
     ) -> Self {
         Self {
-            name: version.to_string(),
+            name: config.get_release_name(&version.to_string()),
             tag: config.get_tag_name(&version.to_string(), prefix),
             version,
             version_files,
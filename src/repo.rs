@@ -1,5 +1,5 @@
 use crate::utils::delete_branch_with_retry;
-use crate::utils::ObjectKind;
+use crate::utils::shell_quote;
 use crate::utils::PathExt;
 
 use anyhow::{bail, format_err, Context, Result};
@@ -7,7 +7,13 @@ use git2::{Branch, BranchType, Oid, StatusOptions};
 use log::info;
 use std::path::Path;
 use std::path::PathBuf;
-use strum::IntoEnumIterator;
+
+/// A branch whose changes were merged into another branch
+pub struct MergedBranch {
+    pub name: String,
+    /// Whether it was squash merged or rebased, rather than merged as is
+    pub squashed: bool,
+}
 
 pub struct Repository {
     repo: git2::Repository,
@@ -24,13 +30,7 @@ impl Repository {
         let repo = git2::Repository::open(&path).context("Failed opening repository")?;
         log::debug!("Repository opened. Reported path is {:?}", repo.path());
 
-        let returned = Self { repo, path };
-
-        if returned.is_dirty()? {
-            bail!("Repository is dirty!");
-        }
-
-        Ok(returned)
+        Ok(Self { repo, path })
     }
 
     pub fn is_path_ignored(&self, path: &Path) -> Result<bool> {
@@ -48,13 +48,36 @@ impl Repository {
             .is_empty())
     }
 
-    pub fn commit_all(&self, message: &str) -> Result<()> {
-        self.path().shell(format!("git commit -a -m {:?}", message))
+    /// Stashes tracked and untracked changes, returning whether there was anything to stash
+    pub fn stash_push(&self, message: &str) -> Result<bool> {
+        if !self.is_dirty()? {
+            return Ok(false);
+        }
+        self.path.shell(format!(
+            "git stash push --include-untracked -m {}",
+            shell_quote(message)
+        ))?;
+        Ok(true)
     }
 
-    pub fn merge_branch_name(&self, branch_name: &str, message: &str) -> Result<()> {
+    pub fn stash_pop(&self) -> Result<()> {
+        self.path.shell("git stash pop")
+    }
+
+    pub fn commit_all(&self, message: &str, sign: bool) -> Result<()> {
+        let flags = if sign { "-S " } else { "" };
         self.path()
-            .shell(format!("git merge {} -m {:?}", branch_name, message))
+            .shell(format!("git commit {flags}-a -m {}", shell_quote(message)))
+    }
+
+    pub fn merge_branch_name(&self, branch_name: &str, message: &str, sign: bool) -> Result<()> {
+        // a fast-forward creates no commit to carry the signature
+        let flags = if sign { "--no-ff -S " } else { "" };
+        self.path().shell(format!(
+            "git merge {flags}{} -m {}",
+            branch_name,
+            shell_quote(message)
+        ))
     }
 
     pub fn delete_branch_name(&self, branch_name: &str) -> Result<()> {
@@ -66,9 +89,199 @@ impl Repository {
         self.path().shell(format!("git tag -d {}", tag_name))
     }
 
-    pub fn create_tag(&self, tag_name: &str) -> Result<()> {
-        self.path()
-            .shell(format!("git tag -a -m {0} {0}", tag_name))
+    pub fn create_tag(&self, tag_name: &str, message: &str, sign: bool) -> Result<()> {
+        let flags = if sign { "-s" } else { "-a" };
+        self.path().shell(format!(
+            "git tag {flags} -m {} {}",
+            shell_quote(message),
+            tag_name
+        ))
+    }
+
+    /// Creates an annotated (or signed) tag pointing at the commit `target` refers to
+    pub fn create_tag_at(
+        &self,
+        tag_name: &str,
+        target: &str,
+        message: &str,
+        sign: bool,
+    ) -> Result<()> {
+        let flags = if sign { "-s" } else { "-a" };
+        self.path().shell(format!(
+            "git tag {flags} -m {} {} {}",
+            shell_quote(message),
+            tag_name,
+            target
+        ))
+    }
+
+    /// Commits a revert of the changes between `base` and `tip` on the current branch. Returns false if there were no
+    /// changes to revert
+    pub fn revert_changes(&self, base: &str, tip: &str, message: &str, sign: bool) -> Result<bool> {
+        if self
+            .path()
+            .shell(format!("git diff --quiet {} {}", tip, base))
+            .is_ok()
+        {
+            return Ok(false);
+        }
+        self.path().shell(format!(
+            "git diff --binary {} {} | git apply --3way --index",
+            tip, base
+        ))?;
+        self.commit_all(message, sign)?;
+        Ok(true)
+    }
+
+    pub fn is_merge_commit(&self, commit: &str) -> Result<bool> {
+        Ok(self
+            .repo
+            .revparse_single(commit)?
+            .peel_to_commit()?
+            .parent_count()
+            > 1)
+    }
+
+    /// Discards the changes to the current branch, resetting it to `commit`
+    pub fn reset_hard(&self, commit: &str) -> Result<()> {
+        self.path().shell(format!("git reset --hard {}", commit))
+    }
+
+    /// Finds the merge commit in the first parent history of `branch_name` which merged `commit` into it
+    pub fn find_merge_of(&self, branch_name: &str, commit: &str) -> Result<Option<String>> {
+        let commit = self.repo.revparse_single(commit)?.peel_to_commit()?.id();
+        let mut history = self.repo.revwalk()?;
+        history.push(self.find_branch(branch_name)?.get().peel_to_commit()?.id())?;
+        history.simplify_first_parent()?;
+        for oid in history {
+            let candidate = self.repo.find_commit(oid?)?;
+            if candidate
+                .parent_ids()
+                .skip(1)
+                .any(|parent| parent == commit)
+            {
+                return Ok(Some(candidate.id().to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Attaches a note to the commit `target` refers to, under `notes_ref` (e.g. "refs/notes/pargit"), replacing an
+    /// existing note
+    pub fn set_note(&self, notes_ref: &str, target: &str, note: &str) -> Result<()> {
+        let commit = self.repo.revparse_single(target)?.peel_to_commit()?.id();
+        let signature = self.repo.signature()?;
+        self.repo
+            .note(&signature, &signature, Some(notes_ref), commit, note, true)?;
+        Ok(())
+    }
+
+    pub fn read_note(&self, notes_ref: &str, target: &str) -> Result<Option<String>> {
+        let commit = self.repo.revparse_single(target)?.peel_to_commit()?.id();
+        match self.repo.find_note(Some(notes_ref), commit) {
+            Ok(note) => Ok(note.message().map(String::from)),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Fetches the remote's notes under `notes_ref`, if it has any, into `remote_notes_ref` and merges them into the
+    /// local ones. Local notes (e.g. ones that failed to push) win over the remote's for the same commit
+    pub fn fetch_notes(&self, remote_name: &str, notes_ref: &str) -> Result<()> {
+        let output = self
+            .path()
+            .shell_output(format!("git ls-remote {} {}", remote_name, notes_ref))?;
+        if String::from_utf8_lossy(&output.stdout).trim().is_empty() {
+            return Ok(());
+        }
+        let remote_notes_ref = remote_notes_ref(remote_name, notes_ref);
+        self.path().shell(format!(
+            "git fetch {} +{}:{}",
+            remote_name, notes_ref, remote_notes_ref
+        ))?;
+        self.path().shell(format!(
+            "git notes --ref={} merge -q -s ours {}",
+            notes_ref, remote_notes_ref
+        ))
+    }
+
+    /// Creates or moves a lightweight tag to point at the commit `target` refers to
+    pub fn force_lightweight_tag(&self, tag_name: &str, target: &str) -> Result<()> {
+        let commit = self.repo.revparse_single(target)?.peel_to_commit()?;
+        self.repo
+            .tag_lightweight(tag_name, commit.as_object(), true)?;
+        Ok(())
+    }
+
+    /// Counts the commits reachable from `branch_name` but not from `base_name`
+    pub fn count_commits_between(&self, base_name: &str, branch_name: &str) -> Result<usize> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(
+            self.repo
+                .revparse_single(branch_name)?
+                .peel_to_commit()?
+                .id(),
+        )?;
+        revwalk.hide(self.repo.revparse_single(base_name)?.peel_to_commit()?.id())?;
+        Ok(revwalk.count())
+    }
+
+    /// Makes sure a key for signing tags and commits is available, according to the `user.signingkey` and
+    /// `gpg.format` git configuration
+    pub fn check_signing_key(&self) -> Result<()> {
+        let config = self
+            .repo
+            .config()
+            .context("Failed reading git configuration")?;
+        let format = config
+            .get_string("gpg.format")
+            .unwrap_or_else(|_| "openpgp".into());
+        let key = config.get_string("user.signingkey").ok();
+
+        match format.as_str() {
+            "ssh" => {
+                let key = key.ok_or_else(|| {
+                    format_err!("Signing with SSH requires user.signingkey to be configured")
+                })?;
+                if key.starts_with("key::") || key.starts_with("ssh-") {
+                    return Ok(());
+                }
+                let path = match key.strip_prefix("~/") {
+                    Some(rest) => {
+                        PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(rest)
+                    }
+                    None => PathBuf::from(&key),
+                };
+                if !path.exists() {
+                    bail!("SSH signing key {path:?} (user.signingkey) does not exist");
+                }
+            }
+            "openpgp" => {
+                let program = config
+                    .get_string("gpg.openpgp.program")
+                    .or_else(|_| config.get_string("gpg.program"))
+                    .unwrap_or_else(|_| "gpg".into());
+                let key = match key {
+                    Some(key) => key,
+                    None => config.get_string("user.email").map_err(|_| {
+                        format_err!("Signing requires either user.signingkey or user.email to be configured")
+                    })?,
+                };
+                self.path()
+                    .shell(format!(
+                        "{} --list-secret-keys {}",
+                        shell_quote(&program),
+                        shell_quote(&key)
+                    ))
+                    .with_context(|| format!("No secret GPG key found for {key:?}"))?;
+            }
+            _ => {
+                if key.is_none() {
+                    bail!("Signing with {format} requires user.signingkey to be configured");
+                }
+            }
+        }
+        Ok(())
     }
 
     pub fn tags(&self) -> Result<Vec<String>> {
@@ -90,10 +303,75 @@ impl Repository {
             .collect())
     }
 
+    /// Returns the paths of all files tracked in the index, relative to the repository root
+    pub fn tracked_paths(&self) -> Result<Vec<PathBuf>> {
+        Ok(self
+            .repo
+            .index()
+            .context("Failed reading index")?
+            .iter()
+            .filter_map(|entry| String::from_utf8(entry.path).ok())
+            .map(PathBuf::from)
+            .collect())
+    }
+
     fn is_merged(&self, commit: Oid, branch: Oid) -> Result<bool> {
         Ok(self.repo.merge_base(commit, branch)? == commit)
     }
 
+    /// Returns whether the commit `ancestor` refers to is reachable from `descendant`
+    pub fn contains(&self, descendant: &str, ancestor: &str) -> Result<bool> {
+        let descendant = self
+            .repo
+            .revparse_single(descendant)?
+            .peel_to_commit()?
+            .id();
+        let ancestor = self.repo.revparse_single(ancestor)?.peel_to_commit()?.id();
+        self.is_merged(ancestor, descendant)
+    }
+
+    pub fn resolve_commit(&self, rev: &str) -> Result<String> {
+        Ok(self
+            .repo
+            .revparse_single(rev)?
+            .peel_to_commit()?
+            .id()
+            .to_string())
+    }
+
+    pub fn remote_url(&self, remote_name: &str) -> Option<String> {
+        self.repo
+            .find_remote(remote_name)
+            .ok()
+            .and_then(|remote| remote.url().map(String::from))
+    }
+
+    /// The configured git identity, e.g. "Jane Doe <jane@example.com>"
+    pub fn user_identity(&self) -> Option<String> {
+        let config = self.repo.config().ok()?;
+        let name = config.get_string("user.name").ok();
+        let email = config.get_string("user.email").ok();
+        match (name, email) {
+            (Some(name), Some(email)) => Some(format!("{name} <{email}>")),
+            (name, email) => name.or(email),
+        }
+    }
+
+    pub fn set_branch_config(&self, branch_name: &str, key: &str, value: &str) -> Result<()> {
+        self.repo
+            .config()?
+            .set_str(&format!("branch.{branch_name}.{key}"), value)?;
+        Ok(())
+    }
+
+    pub fn get_branch_config(&self, branch_name: &str, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .repo
+            .config()?
+            .get_string(&format!("branch.{branch_name}.{key}"))
+            .ok())
+    }
+
     pub fn is_branch_up_to_date(&self, branch_name: &str) -> Result<bool> {
         self.git_fetch("origin")?;
         let branch = self.find_branch(branch_name)?;
@@ -116,49 +394,155 @@ impl Repository {
         Ok(())
     }
 
-    pub fn cleanup(&self, develop_branch_name: &str) -> Result<()> {
-        self.git_fetch("origin")?;
-        let develop_branch = self.find_branch(develop_branch_name)?;
-        let remote_develop = develop_branch
-            .upstream()?
-            .into_reference()
+    /// Finds branches (local, or remote-tracking such as origin/feature/x) starting with one of `prefixes`, whose
+    /// changes were merged into `target` (e.g. "origin/develop"), either directly or by squashing or rebasing their
+    /// commits. Branches whose tip is reachable from the local `develop_branch_name` (e.g. freshly started ones, or a
+    /// hotfix started from a main branch that was merged back) are skipped
+    pub fn find_merged_branches(
+        &self,
+        branch_type: BranchType,
+        prefixes: &[String],
+        develop_branch_name: &str,
+        target: &str,
+    ) -> Result<Vec<MergedBranch>> {
+        let target = self.repo.revparse_single(target)?.peel_to_commit()?.id();
+        let develop = self
+            .find_branch(develop_branch_name)?
+            .get()
             .peel_to_commit()?
             .id();
-        let develop = develop_branch.get().peel_to_commit()?.id();
-        let current_branch_name = self.current_branch_name()?;
-
-        // we only cleanup if remote_develop is ahead of develop and  contains it
-        if develop != remote_develop && self.is_merged(develop, remote_develop)? {
-            info!("Remote develop branch is ahead of local develop branch");
-            for branch in self.repo.branches(Some(BranchType::Local))? {
-                let (mut branch, _) = branch?;
-                let name = branch.name()?.unwrap();
-                if ObjectKind::iter().any(|kind| name.starts_with(format!("{}/", kind).as_str())) {
-                    let branch_commit = branch.get().peel_to_commit()?.id();
-                    if self.is_merged(branch_commit, remote_develop)?
-                        && !self.is_merged(branch_commit, develop)?
-                    {
-                        info!("Branch {} is not merged into local develop, but is merged to remote develop. Deleting...", name);
-                        if current_branch_name == name {
-                            info!(
-                                "Branch {current_branch_name} is the current branch, switching to {}...",
-                                develop_branch.name()?.unwrap()
-                            );
-                            self.switch_to_branch(&develop_branch)?;
-                        }
-                        delete_branch_with_retry(&mut branch)?;
-                    }
+
+        let mut returned = Vec::new();
+        for branch in self.repo.branches(Some(branch_type))? {
+            let (branch, _) = branch?;
+            let name = match branch.name()? {
+                Some(name)
+                    if prefixes
+                        .iter()
+                        .any(|prefix| name.starts_with(prefix.as_str())) =>
+                {
+                    name
                 }
+                _ => continue,
+            };
+            let branch_commit = branch.get().peel_to_commit()?.id();
+            if self.is_merged(branch_commit, develop)? {
+                continue;
             }
-            info!("Pulling develop branch from remote (ff-only)...");
-            self.pull_current_branch(true)?;
-        } else {
-            info!("Remote develop branch is not ahead of local develop branch. Not doing anything");
+            let squashed = if self.is_merged(branch_commit, target)? {
+                false
+            } else if self.contains_changes(branch_commit, target)? {
+                true
+            } else {
+                continue;
+            };
+            returned.push(MergedBranch {
+                name: name.to_owned(),
+                squashed,
+            });
         }
-        Ok(())
+        Ok(returned)
+    }
+
+    /// Whether the remote branch a local branch tracks has commits the local branch doesn't
+    pub fn is_behind_upstream(&self, branch_name: &str) -> Result<bool> {
+        let branch = self.find_branch(branch_name)?;
+        let local = branch.get().peel_to_commit()?.id();
+        let remote = branch.upstream()?.into_reference().peel_to_commit()?.id();
+        Ok(local != remote && !self.is_merged(remote, local)?)
+    }
+
+    /// The time of the latest commit of each branch (local, or remote-tracking such as origin/release/1.0.0) starting
+    /// with one of `prefixes`, in seconds since the epoch
+    pub fn branch_commit_times(
+        &self,
+        branch_type: BranchType,
+        prefixes: &[String],
+    ) -> Result<Vec<(String, i64)>> {
+        let mut returned = Vec::new();
+        for branch in self.repo.branches(Some(branch_type))? {
+            let (branch, _) = branch?;
+            if let Some(name) = branch.name()?.filter(|name| {
+                prefixes
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix.as_str()))
+            }) {
+                let time = branch.get().peel_to_commit()?.time().seconds();
+                returned.push((name.to_owned(), time));
+            }
+        }
+        Ok(returned)
+    }
+
+    /// Remote-tracking branches (e.g. origin/feature/x) whose branches no longer exist on the remote
+    pub fn stale_remote_branches(&self, remote_name: &str) -> Result<Vec<String>> {
+        let output = self
+            .path()
+            .shell_output(format!("git remote prune --dry-run {}", remote_name))?;
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("* [would prune] "))
+            .map(String::from)
+            .collect())
+    }
+
+    pub fn prune_remote(&self, remote_name: &str) -> Result<()> {
+        self.path()
+            .shell(format!("git remote prune {}", remote_name))
+    }
+
+    pub fn delete_remote_branches(&self, remote_name: &str, branch_names: &[String]) -> Result<()> {
+        self.path().shell(format!(
+            "git push {} --delete {}",
+            remote_name,
+            branch_names.join(" ")
+        ))
+    }
+
+    /// Finds the commit in the first parent history of `target` where the changes of the commit `rev` refers to landed,
+    /// whether it was merged directly (the commit itself), squash merged or rebased onto `target`
+    pub fn find_landed_commit(&self, rev: &str, target: &str) -> Result<Option<String>> {
+        let commit = self.repo.revparse_single(rev)?.peel_to_commit()?.id();
+        let target = self.repo.revparse_single(target)?.peel_to_commit()?.id();
+        if self.is_merged(commit, target)? {
+            return Ok(Some(commit.to_string()));
+        }
+        let base = self.repo.merge_base(commit, target)?;
+        let mut history = self.repo.revwalk()?;
+        history.push(target)?;
+        history.simplify_first_parent()?;
+        // the changes landed at the oldest commit of the uninterrupted run (from the tip) already containing them
+        let mut landed = None;
+        for oid in history {
+            let oid = oid?;
+            if oid == base || !self.contains_changes(commit, oid)? {
+                break;
+            }
+            landed = Some(oid.to_string());
+        }
+        Ok(landed)
     }
 
-    fn git_fetch(&self, remote_name: &str) -> Result<()> {
+    /// Returns whether the changes a commit introduced since forking from `target` are already contained in `target`.
+    /// Besides merged commits, this recognizes branches that were squash merged or rebased onto `target`
+    fn contains_changes(&self, commit: Oid, target: Oid) -> Result<bool> {
+        let base = self.repo.merge_base(commit, target)?;
+        if base == commit {
+            return Ok(true);
+        }
+        let tree = |oid| self.repo.find_commit(oid).and_then(|commit| commit.tree());
+        let target_tree = tree(target)?;
+        // merging the commit into the target changes nothing if the target already has its changes
+        let mut merged = self
+            .repo
+            .merge_trees(&tree(base)?, &target_tree, &tree(commit)?, None)?;
+        if merged.has_conflicts() {
+            return Ok(false);
+        }
+        Ok(merged.write_tree_to(&self.repo)? == target_tree.id())
+    }
+
+    pub fn git_fetch(&self, remote_name: &str) -> Result<()> {
         info!("Fetching remote {:?}...", remote_name);
         self.path().shell(format!("git fetch {}", remote_name))
     }
@@ -180,6 +564,30 @@ impl Repository {
             .any(|tag| tag == Some(tag_name)))
     }
 
+    /// Looks up a tag on the remote without fetching it into the local tags, returning a description of who created it
+    /// and which commit it points at
+    pub fn find_remote_tag(&self, remote_name: &str, tag_name: &str) -> Result<Option<String>> {
+        let refspec = format!("refs/tags/{tag_name}");
+        let output = self
+            .path()
+            .shell_output(format!("git ls-remote --tags {remote_name} {refspec}"))?;
+        if String::from_utf8_lossy(&output.stdout).trim().is_empty() {
+            return Ok(None);
+        }
+
+        self.path()
+            .shell(format!("git fetch --no-tags {remote_name} {refspec}"))?;
+        let object = self.repo.revparse_single("FETCH_HEAD")?;
+        let commit = object.peel_to_commit()?;
+        let tagger = object.as_tag().and_then(|tag| tag.tagger());
+        let owner = tagger.as_ref().unwrap_or(&commit.author()).to_string();
+        Ok(Some(format!(
+            "created by {owner}, pointing at commit {} ({:?})",
+            &commit.id().to_string()[..10],
+            commit.summary().unwrap_or_default()
+        )))
+    }
+
     pub fn switch_to_branch_name(&self, branch_name: &str) -> Result<()> {
         self.switch_to_branch(&self.find_branch(branch_name)?)
             .with_context(|| format!("Unable to switch to branch {}", branch_name))
@@ -215,7 +623,91 @@ impl Repository {
             .branch(branch_name.as_ref(), &start_point, force)?)
     }
 
+    pub fn is_head_detached(&self) -> Result<bool> {
+        Ok(self.repo.head_detached()?)
+    }
+
+    pub fn is_shallow(&self) -> bool {
+        self.repo.is_shallow()
+    }
+
+    /// Names of all local branches starting with `prefix`
+    pub fn local_branch_names(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut returned = Vec::new();
+        for branch in self.repo.branches(Some(BranchType::Local))? {
+            let (branch, _) = branch?;
+            if let Some(name) = branch.name()?.filter(|name| name.starts_with(prefix)) {
+                returned.push(name.to_owned());
+            }
+        }
+        Ok(returned)
+    }
+
+    /// The name of the remote branch a local branch tracks, e.g. "origin/develop"
+    pub fn branch_upstream_name(&self, branch_name: &str) -> Result<Option<String>> {
+        match self.find_branch(branch_name)?.upstream() {
+            Ok(upstream) => Ok(upstream.name()?.map(String::from)),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The directory git runs hooks from, according to `core.hooksPath`
+    pub fn hooks_path(&self) -> PathBuf {
+        match self
+            .repo
+            .config()
+            .and_then(|config| config.get_path("core.hooksPath"))
+        {
+            Ok(path) => self.path.join(path),
+            Err(_) => self.repo.path().join("hooks"),
+        }
+    }
+
+    /// The branch the remote's HEAD points at (i.e. its default branch), as recorded when cloning it
+    pub fn remote_head_branch(&self, remote_name: &str) -> Option<String> {
+        let prefix = format!("refs/remotes/{remote_name}/");
+        self.repo
+            .find_reference(&format!("{prefix}HEAD"))
+            .ok()?
+            .symbolic_target()?
+            .strip_prefix(&prefix)
+            .map(String::from)
+    }
+
+    /// Guesses the main branch from the remote's default branch, or else from local branches named main or master.
+    /// Git-flow repositories often make the develop branch the remote's default, in which case it is skipped. Returns
+    /// the branch name along with where it was found
+    pub fn detect_main_branch(
+        &self,
+        remote_name: &str,
+        develop_branch_name: &str,
+    ) -> Option<(String, String)> {
+        if let Some(name) = self
+            .remote_head_branch(remote_name)
+            .filter(|name| name != develop_branch_name)
+        {
+            return Some((name, format!("{remote_name}/HEAD")));
+        }
+        ["main", "master"]
+            .iter()
+            .find(|name| self.find_branch(name).is_ok())
+            .map(|name| (name.to_string(), "local branches".into()))
+    }
+
+    pub fn has_remote_branch(&self, remote_name: &str, branch_name: &str) -> bool {
+        self.repo
+            .find_branch(&format!("{remote_name}/{branch_name}"), BranchType::Remote)
+            .is_ok()
+    }
+
     pub fn find_branch(&self, name: impl AsRef<str>) -> Result<Branch<'_>> {
         Ok(self.repo.find_branch(name.as_ref(), BranchType::Local)?)
     }
 }
+
+/// Returns the ref the remote's notes under `notes_ref` are fetched into, e.g. refs/notes/remotes/origin/pargit
+pub fn remote_notes_ref(remote_name: &str, notes_ref: &str) -> String {
+    let name = notes_ref.strip_prefix("refs/notes/").unwrap_or(notes_ref);
+    format!("refs/notes/remotes/{remote_name}/{name}")
+}
@@ -52,6 +52,76 @@ impl<T: AsRef<Path>> PathExt for T {
     }
 }
 
+/// Quotes a string for safe use as a single argument in `sh` commands
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Checks a branch name against git's ref name rules (see git-check-ref-format), describing the first violation
+pub fn check_branch_name(name: &str) -> std::result::Result<(), String> {
+    if name.is_empty() {
+        return Err("it is empty".into());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| c.is_ascii_control() || " ~^:?*[\\".contains(*c))
+    {
+        return Err(match c {
+            ' ' => "it contains spaces (use --slugify to replace them)".into(),
+            c if c.is_ascii_control() => "it contains control characters".into(),
+            c => format!("it contains {c:?}"),
+        });
+    }
+    for forbidden in ["..", "@{", "//"] {
+        if name.contains(forbidden) {
+            return Err(format!("it contains {forbidden:?}"));
+        }
+    }
+    if name.starts_with('/') || name.ends_with('/') || name.ends_with('.') {
+        return Err("it starts or ends with a slash, or ends with a dot".into());
+    }
+    if name == "@" {
+        return Err("\"@\" is reserved".into());
+    }
+    if name
+        .split('/')
+        .any(|component| component.starts_with('.') || component.ends_with(".lock"))
+    {
+        return Err("a part of it starts with a dot or ends with \".lock\"".into());
+    }
+    Ok(())
+}
+
+/// Turns free text into a branch name, e.g. "Add login page" into "add-login-page"
+pub fn slugify(s: &str) -> String {
+    s.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Formats seconds since the epoch as an RFC 3339 UTC timestamp, e.g. "2022-03-01T12:30:00Z"
+pub fn format_utc_timestamp(secs: i64) -> String {
+    let (days, secs) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+    // converts days since the epoch to a civil date (see http://howardhinnant.github.io/date_algorithms.html)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
 pub fn next_version(version: &Version, bump_kind: BumpKind) -> Version {
     let mut version = version.clone();
     match bump_kind {
@@ -173,3 +243,42 @@ impl ObjectKind {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{check_branch_name, format_utc_timestamp, slugify};
+
+    #[test]
+    fn test_check_branch_name() {
+        for valid in ["feature/PROJ-123-login", "release/1.2.0", "a/b.c"] {
+            assert_eq!(check_branch_name(valid), Ok(()), "{}", valid);
+        }
+        for invalid in [
+            "feature/add login",
+            "feature/a..b",
+            "feature/.hidden",
+            "feature/x.lock",
+            "feature/x.",
+            "feature/a//b",
+            "feature/what?",
+            "feature/a@{b",
+            "feature/",
+        ] {
+            assert!(check_branch_name(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Add login page"), "add-login-page");
+        assert_eq!(slugify("  Fix: crash (again!) "), "fix-crash-again");
+        assert_eq!(slugify("PROJ-123 Do it"), "proj-123-do-it");
+    }
+
+    #[test]
+    fn test_format_utc_timestamp() {
+        assert_eq!(format_utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc_timestamp(951782400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc_timestamp(1646137800), "2022-03-01T12:30:00Z");
+    }
+}
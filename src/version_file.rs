@@ -7,6 +7,7 @@ use std::path::{Path, PathBuf};
 pub enum VersionFile {
     CargoToml {
         path: PathBuf,
+        name: Option<String>,
         version: Version,
         is_workspace: bool,
     },
@@ -32,6 +33,7 @@ impl VersionFile {
                 path,
                 version,
                 is_workspace,
+                ..
             } => {
                 debug!("Bumping Cargo.toml file {:?}", path);
 
@@ -50,6 +52,12 @@ impl VersionFile {
         }
     }
 
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            VersionFile::CargoToml { name, .. } => name.as_deref(),
+        }
+    }
+
     pub fn path(&self) -> &Path {
         match self {
             VersionFile::CargoToml { path, .. } => path,
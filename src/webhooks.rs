@@ -0,0 +1,61 @@
+use crate::config::WebhookConfig;
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info, warn};
+use serde::Serialize;
+use std::time::Duration;
+
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Serialize)]
+pub struct WebhookPayload {
+    /// The kind and action joined, e.g. "release_finish"
+    pub event: String,
+    pub action: &'static str,
+    pub kind: String,
+    pub name: String,
+    pub project: Option<String>,
+    pub version: Option<String>,
+    pub tag: Option<String>,
+    pub commit: String,
+    pub remote: Option<String>,
+    pub user: Option<String>,
+}
+
+/// Sends the payload to all webhooks interested in it. Delivery failures are only logged, as the notified operation
+/// already took place
+pub fn notify(webhooks: &[WebhookConfig], payload: &WebhookPayload) {
+    for webhook in webhooks
+        .iter()
+        .filter(|webhook| webhook.accepts(&payload.kind, payload.action))
+    {
+        info!("Notifying {} of {}", webhook.url, payload.event);
+        if let Err(e) = deliver(webhook, payload) {
+            warn!("Failed notifying webhook {}: {e:?}", webhook.url);
+        }
+    }
+}
+
+fn deliver(webhook: &WebhookConfig, payload: &WebhookPayload) -> Result<()> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(webhook.timeout_secs))
+        .build();
+    let mut attempt = 0;
+    loop {
+        debug!("POST {}", webhook.url);
+        let error = match agent.post(&webhook.url).send_json(payload) {
+            Ok(_) => return Ok(()),
+            // client errors won't go away by retrying, except for rate limiting
+            Err(ureq::Error::Status(status, _)) if status < 500 && status != 429 => {
+                bail!("Webhook responded with status {status}")
+            }
+            Err(e) => e,
+        };
+        if attempt >= webhook.retries {
+            return Err(error).context(format!("Giving up after {} attempts", attempt + 1));
+        }
+        attempt += 1;
+        warn!("Delivery to {} failed ({error}), retrying...", webhook.url);
+        std::thread::sleep(RETRY_DELAY * attempt);
+    }
+}
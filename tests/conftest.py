@@ -1,4 +1,7 @@
 import os
+import json
+import threading
+from http.server import BaseHTTPRequestHandler, HTTPServer
 from uuid import uuid4
 import subprocess
 import pathlib
@@ -169,6 +172,39 @@ def submodule_pargit(submodule, pargit_binary, submodule_branch_config):
     return returned
 
 
+class RecordingHandler(BaseHTTPRequestHandler):
+    def do_POST(self):
+        body = self.rfile.read(int(self.headers["Content-Length"]))
+        self.server.requests.append((self.path, dict(self.headers), body))
+        status = self.server.statuses.pop(0) if self.server.statuses else self.server.status
+        self.send_response(status)
+        if status >= 300 or self.server.response is None:
+            self.end_headers()
+            return
+        self.send_header("Content-Type", "application/json")
+        self.end_headers()
+        self.wfile.write(json.dumps(self.server.response).encode())
+
+    def log_message(self, *args):
+        pass
+
+
+@pytest.fixture
+def http_server():
+    """A local HTTP server recording the (path, headers, body) of each POST request. Responds with `status` (or the
+    queued `statuses` first) and the JSON `response`, if set"""
+    server = HTTPServer(("127.0.0.1", 0), RecordingHandler)
+    server.url = f"http://127.0.0.1:{server.server_address[1]}"
+    server.requests = []
+    server.status = 200
+    server.statuses = []
+    server.response = None
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    yield server
+    server.shutdown()
+
+
 @pytest.fixture
 def develop_branch(branch_config) -> str:
     return branch_config.develop_branch_name
@@ -373,7 +409,7 @@ class Pargit:
         if kwargs.pop("capture", False):
             kwargs["stdout"] = subprocess.PIPE
             kwargs["stderr"] = subprocess.PIPE
-        subprocess.run(
+        return subprocess.run(
             f'{self.binary} {" ".join(args)}',
             shell=True,
             cwd=self.repo.path,
@@ -0,0 +1,90 @@
+import pytest
+
+from conftest import Pargit
+
+
+@pytest.fixture
+def alias_pargit(pargit):
+    pargit.repo.configure_pargit({"tag_prefix": "v", "alias_tags": ["major", "minor"]})
+    pargit.repo.commit_all_changes()
+    pargit.repo.tag("v1.3.0")
+    return pargit
+
+
+def _sha(repo, ref):
+    return repo.shell_output(f"git rev-parse {ref}^{{commit}}").strip()
+
+
+def _remote_tags(repo):
+    return {
+        line.split("refs/tags/")[1]
+        for line in repo.shell_output("git ls-remote --tags origin").splitlines()
+        if not line.endswith("^{}")
+    }
+
+
+def test_alias_tags_follow_releases(alias_pargit):
+    repo = alias_pargit.repo
+    alias_pargit.release_version_minor()
+    assert _sha(repo, "v1") == _sha(repo, "v1.4.0")
+    assert _sha(repo, "v1.4") == _sha(repo, "v1.4.0")
+    assert {"v1", "v1.4"} <= _remote_tags(repo)
+
+    repo.commit_change()
+    alias_pargit.release_version("patch")
+    assert _sha(repo, "v1") == _sha(repo, "v1.4.1")
+    assert _sha(repo, "v1.4") == _sha(repo, "v1.4.1")
+
+
+def test_alias_tags_not_moved_for_prerelease(alias_pargit):
+    alias_pargit.release_version("1.4.0-rc.1")
+    assert "v1" not in alias_pargit.repo.tags()
+
+
+def test_alias_tags_not_moved_backwards(alias_pargit, main_branch):
+    repo = alias_pargit.repo
+    alias_pargit.release_version_minor()
+    support_commit = _sha(repo, "v1.3.0")
+    repo.shell(f"git checkout -b support {support_commit}")
+    repo.commit_change()
+    fix_commit = _sha(repo, "HEAD")
+    repo.switch_to_branch(main_branch)
+    repo.delete_branch("support")
+
+    alias_pargit.hotfix_start("1.3.1", f"--from-ref {fix_commit}")
+    alias_pargit.hotfix_finish()
+
+    assert _sha(repo, "v1") == _sha(repo, "v1.4.0")
+    assert _sha(repo, "v1.3") == _sha(repo, "v1.3.1")
+
+
+def test_alias_tag_push_failure_keeps_release(alias_pargit, remote_repo):
+    hook = remote_repo.path / "hooks" / "update"
+    with hook.open("w") as f:
+        f.write('#!/bin/sh\n[ "$1" != refs/tags/v1 ]\n')
+    hook.chmod(0o755)
+
+    alias_pargit.release_version_minor()
+
+    assert "v1.4.0" in _remote_tags(alias_pargit.repo)
+    assert "v1" not in _remote_tags(alias_pargit.repo)
+
+
+def test_release_from_clone_with_stale_alias_tags(
+    alias_pargit, remote_repo, main_branch, develop_branch, tmpdir
+):
+    alias_pargit.release_version_minor()
+    clone = remote_repo.clone_to(tmpdir / "another_clone")
+    clone.shell(f"git checkout {main_branch}")
+    clone.switch_to_branch(develop_branch)
+    alias_pargit.repo.commit_change()
+    alias_pargit.release_version_minor()
+
+    # pulling doesn't move the clone's existing v1 tag to v1.5.0
+    clone.shell("git pull --ff-only")
+    clone.commit_change()
+    Pargit(alias_pargit.binary, clone).release_version_minor()
+
+    assert clone.branches() == {main_branch, develop_branch}
+    assert {"v1", "v1.6", "v1.6.0"} <= _remote_tags(clone)
+    assert _sha(clone, "v1") == _sha(clone, "v1.6.0")
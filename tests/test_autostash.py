@@ -0,0 +1,61 @@
+import subprocess
+
+import pytest
+
+
+@pytest.fixture
+def dirty_pargit(pargit):
+    (pargit.repo.path / "tracked.txt").write_text("original")
+    pargit.repo.commit_all_changes()
+    (pargit.repo.path / "tracked.txt").write_text("modified")
+    (pargit.repo.path / "untracked.txt").write_text("untracked")
+    return pargit
+
+
+def _assert_changes_kept(repo):
+    assert (repo.path / "tracked.txt").read_text() == "modified"
+    assert (repo.path / "untracked.txt").read_text() == "untracked"
+    assert repo.shell_output("git stash list").strip() == ""
+
+
+def test_dirty_tree_refused(dirty_pargit):
+    with pytest.raises(subprocess.CalledProcessError):
+        dirty_pargit.feature_start("blap")
+
+
+def test_untracked_files_allowed(pargit):
+    (pargit.repo.path / "untracked.txt").write_text("untracked")
+    pargit.feature_start("blap")
+
+
+def test_publish_allowed_on_dirty_tree(dirty_pargit):
+    dirty_pargit.repo.shell("git stash")
+    dirty_pargit.feature_start("blap")
+    dirty_pargit.repo.shell("git stash pop")
+    dirty_pargit.feature_publish()
+
+
+def test_autostash_restored_after_switching_branches(dirty_pargit):
+    dirty_pargit.feature_start("blap", "--autostash")
+    assert dirty_pargit.repo.current_branch() == "feature/blap"
+    _assert_changes_kept(dirty_pargit.repo)
+
+
+def test_autostash_config(dirty_pargit):
+    dirty_pargit.repo.configure_pargit({"autostash": True})
+    dirty_pargit.feature_start("blap")
+    _assert_changes_kept(dirty_pargit.repo)
+
+
+def test_autostash_kept_on_conflict(dirty_pargit, develop_branch):
+    repo = dirty_pargit.repo
+    repo.shell("git stash")
+    dirty_pargit.feature_start("blap")
+    (repo.path / "tracked.txt").write_text("conflicting")
+    repo.shell("git commit -am conflicting")
+    repo.switch_to_branch(develop_branch)
+    repo.shell("git stash pop")
+
+    dirty_pargit.feature_finish("blap", "--autostash")
+
+    assert "pargit autostash" in repo.shell_output("git stash list")
@@ -0,0 +1,30 @@
+import subprocess
+
+import pytest
+
+
+@pytest.mark.parametrize("name", ["'add login'", "a..b", "x.lock", "'what?'"])
+def test_invalid_feature_name(pargit, name):
+    branches = pargit.repo.branches()
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.feature_start(name, capture=True)
+    assert "Invalid feature name" in caught.value.stderr
+    assert pargit.repo.branches() == branches
+
+
+def test_slugify(pargit):
+    pargit.feature_start("--slugify", "'Add login page'")
+    assert pargit.repo.current_branch() == "feature/add-login-page"
+
+
+def test_feature_name_pattern(pargit):
+    pargit.repo.configure_pargit({"feature_name_pattern": "(?i)^PROJ-[0-9]+-"})
+    pargit.repo.commit_all_changes()
+    branches = pargit.repo.branches()
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.feature_start("login", capture=True)
+    assert "does not match the required pattern" in caught.value.stderr
+    assert pargit.repo.branches() == branches
+
+    pargit.feature_start("--slugify", "'PROJ-123 Login page'")
+    assert pargit.repo.current_branch() == "feature/proj-123-login-page"
@@ -1,3 +1,5 @@
+import json
+
 from conftest import Pargit
 
 
@@ -43,3 +45,80 @@ def test_cleanup_with_remote_changes(
     # Cleanup original repo
     pargit.cleanup()
     assert change.exists()
+
+
+def _squash_into_remote_develop(remote_repo, branch_name, develop_branch, tmpdir):
+    other = remote_repo.clone_to(tmpdir / "squashing_clone")
+    other.switch_to_branch(develop_branch)
+    other.commit_change()
+    other.shell(f"git fetch origin {branch_name}")
+    other.shell("git merge --squash FETCH_HEAD")
+    other.shell("git commit -m squashed")
+    other.shell(f"git push origin {develop_branch}")
+
+
+def test_cleanup_squash_merged_branch(pargit, develop_branch, remote_repo, tmpdir):
+    branch_name = "feature/squashed"
+    pargit.repo.create_branch(branch_name)
+    pargit.repo.switch_to_branch(branch_name)
+    pargit.repo.commit_change()
+    pargit.repo.commit_change()
+    pargit.repo.shell(f"git push origin {branch_name}")
+    _squash_into_remote_develop(remote_repo, branch_name, develop_branch, tmpdir)
+
+    pargit.cleanup("--dry-run")
+    assert branch_name in pargit.repo.branches()
+
+    pargit.cleanup()
+    assert branch_name not in pargit.repo.branches()
+    assert pargit.repo.current_branch() == develop_branch
+
+
+def test_cleanup_remote_branches(pargit, develop_branch, remote_repo, tmpdir):
+    for branch_name in ("feature/squashed", "feature/deleted", "feature/fresh"):
+        pargit.repo.create_branch(branch_name, develop_branch)
+        pargit.repo.switch_to_branch(branch_name)
+        if branch_name != "feature/fresh":
+            pargit.repo.commit_change()
+        pargit.repo.shell(f"git push origin {branch_name}")
+    pargit.repo.switch_to_branch(develop_branch)
+    _squash_into_remote_develop(remote_repo, "feature/squashed", develop_branch, tmpdir)
+    remote_repo.shell("git branch -D feature/deleted")
+
+    result = pargit.cleanup("--remote", "--output", "json", capture=True)
+
+    assert json.loads(result.stdout)["deleted_branches"] == [
+        "feature/squashed",
+        "origin/feature/squashed",
+    ]
+    assert "feature/fresh" in remote_repo.branches()
+    assert "feature/squashed" not in remote_repo.branches()
+    remote_tracking = pargit.repo.shell_output("git branch -r")
+    assert "origin/feature/deleted" not in remote_tracking
+    assert "feature/deleted" in pargit.repo.branches()
+
+
+def test_cleanup_reports_stale_releases(pargit, develop_branch):
+    pargit.repo.configure_pargit({"stale_branch_days": 0})
+    pargit.repo.commit_all_changes()
+    pargit.repo.create_branch("release/0.9.0")
+    result = pargit.cleanup("--output", "json", capture=True)
+    assert json.loads(result.stdout)["stale_branches"] == [
+        {"name": "release/0.9.0", "days": 0}
+    ]
+
+
+def test_cleanup_keeps_fresh_hotfix_branch(pargit, main_branch, develop_branch):
+    pargit.repo.switch_to_branch(main_branch)
+    pargit.repo.commit_change()
+    pargit.repo.shell(f"git push origin {main_branch}")
+    pargit.repo.switch_to_branch(develop_branch)
+    pargit.repo.commit_change()
+    # the main branch is merged back as the second parent
+    pargit.repo.shell(f"git merge --no-ff {main_branch} -m 'Merge back'")
+    pargit.repo.shell(f"git push origin {develop_branch}")
+    pargit.repo.create_branch("hotfix/1.0.1", main_branch)
+
+    pargit.cleanup()
+
+    assert "hotfix/1.0.1" in pargit.repo.branches()
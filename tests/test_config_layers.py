@@ -0,0 +1,53 @@
+import json
+
+import pytest
+
+
+@pytest.fixture
+def user_config(pargit, tmpdir):
+    config_home = tmpdir / "xdg"
+    (config_home / "pargit").ensure(dir=True)
+    pargit.env["XDG_CONFIG_HOME"] = str(config_home)
+    return config_home / "pargit" / "config.toml"
+
+
+def _config(pargit, *args):
+    result = pargit.config_show("--output", "json", *args, capture=True)
+    return {
+        entry["key"]: (entry["value"], entry["origin"])
+        for entry in json.loads(result.stdout)["config"]
+    }
+
+
+def test_config_layers(pargit, user_config):
+    user_config.write("sign_tags = true\ntag_prefix = 'u'\nprotected_main_branch = true\n")
+    pargit.repo.configure_pargit({"tag_prefix": "v"})
+    (pargit.repo.path / ".git" / "pargit.toml").write_text("protected_main_branch = false\n")
+    pargit.env["PARGIT_AUTOSTASH"] = "1"
+
+    config = _config(pargit)
+    assert config["sign_tags"] == (True, str(user_config))
+    assert config["tag_prefix"][0] == "v"
+    assert config["tag_prefix"][1].endswith(".pargit.toml")
+    assert config["protected_main_branch"][0] is False
+    assert config["protected_main_branch"][1].endswith(".git/pargit.toml")
+    assert config["autostash"] == (True, "environment variable PARGIT_AUTOSTASH")
+    assert config["sign_commits"] == (False, "default")
+
+
+def test_config_command_line_overrides(pargit, user_config):
+    pargit.env["PARGIT_AUTOSTASH"] = "0"
+    assert _config(pargit, "--autostash")["autostash"] == (True, "command line")
+
+
+def test_config_show_human(pargit, user_config):
+    user_config.write("sign_tags = true\n")
+    result = pargit.config_show("--origin", capture=True)
+    assert f"sign_tags = true  # {user_config}" in result.stdout.splitlines()
+
+
+def test_user_config_applies_to_releases(pargit, user_config):
+    user_config.write("tag_prefix = 'v'\n")
+    pargit.repo.tag("v0.1.0")
+    pargit.release_version_minor()
+    assert "v0.2.0" in pargit.repo.tags()
@@ -0,0 +1,54 @@
+import subprocess
+
+import pytest
+import toml
+
+
+def test_unknown_key_suggests_known_key(pargit):
+    pargit.repo.configure_pargit({"develop_branch": "dev"})
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.config_show(capture=True)
+    assert caught.value.returncode == 15
+    assert "unknown field `develop_branch`" in caught.value.stderr
+    assert "Did you mean `develop_branch_name`?" in caught.value.stderr
+
+
+def test_unknown_project_key_rejected(pargit):
+    pargit.repo.configure_pargit({"projects": [{"name": "cli", "subpth": "cli"}]})
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.config_show(capture=True)
+    assert caught.value.returncode == 15
+    assert "Did you mean `subpath`?" in caught.value.stderr
+
+
+def test_newer_schema_version_rejected(pargit):
+    pargit.repo.configure_pargit({"schema_version": 1000})
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.config_show(capture=True)
+    assert "Please upgrade pargit" in caught.value.stderr
+
+
+def test_migrate_keeps_comments(pargit, main_branch):
+    config_path = pargit.repo.path / ".pargit.toml"
+    config_path.write_text(
+        "# our branches\n"
+        f'master_branch_name = "{main_branch}"\n'
+        'develop_branch_name = "develop"\n'
+        "# the project lives at the root\n"
+        'project_subpath = "."\n'
+    )
+    result = pargit.config_show(capture=True)
+    assert "pargit config migrate" in result.stderr
+
+    pargit.config_migrate()
+
+    contents = config_path.read_text()
+    assert "# our branches\n" in contents
+    assert "# the project lives at the root\n" in contents
+    config = toml.loads(contents)
+    assert config["main_branch_name"] == main_branch
+    assert config["project"] == {"subpath": "."}
+    assert config["schema_version"] == 2
+    assert "master_branch_name" not in config
+    assert "project_subpath" not in config
+    assert "pargit config migrate" not in pargit.config_show(capture=True).stderr
@@ -0,0 +1,61 @@
+import subprocess
+
+import pytest
+import toml
+
+
+def _config(pargit):
+    return toml.load(pargit.repo.path / ".pargit.toml")
+
+
+def test_configure_creates_develop_branch(pargit, remote_repo, main_branch, develop_branch):
+    repo = pargit.repo
+    repo.switch_to_branch(main_branch)
+    repo.shell(f"git branch -D {develop_branch}")
+    repo.shell(f"git push origin :{develop_branch}")
+
+    pargit.configure(
+        "--non-interactive",
+        "--force",
+        "--create-develop",
+        f"--main-branch {main_branch}",
+        f"--develop-branch {develop_branch}",
+    )
+
+    assert develop_branch in repo.branches()
+    assert develop_branch in remote_repo.branches()
+    config = _config(pargit)
+    assert config["main_branch_name"] == main_branch
+    assert config["develop_branch_name"] == develop_branch
+    assert "tag_prefix" not in config
+
+
+def test_configure_suggests_tag_prefix(pargit, main_branch, develop_branch):
+    pargit.repo.tag("v0.1.0")
+    pargit.configure(
+        "--non-interactive",
+        "--force",
+        f"--main-branch {main_branch}",
+        f"--develop-branch {develop_branch}",
+    )
+    assert _config(pargit)["tag_prefix"] == "v"
+
+
+def test_configure_detects_project_subpath(pargit, main_branch, develop_branch):
+    crate = pargit.repo.path / "crate"
+    crate.mkdir()
+    (crate / "Cargo.toml").write_text('[package]\nname = "crate"\nversion = "0.1.0"\n')
+    pargit.configure(
+        "--non-interactive",
+        "--force",
+        f"--main-branch {main_branch}",
+        f"--develop-branch {develop_branch}",
+    )
+    assert _config(pargit)["project"] == {"subpath": "crate"}
+
+
+def test_configure_does_not_overwrite_config(pargit):
+    before = (pargit.repo.path / ".pargit.toml").read_text()
+    with pytest.raises(subprocess.CalledProcessError):
+        pargit.configure("--non-interactive", capture=True)
+    assert (pargit.repo.path / ".pargit.toml").read_text() == before
@@ -0,0 +1,78 @@
+import pytest
+import toml
+
+
+def _make_fixture_manifest(path, name):
+    path.mkdir(parents=True, exist_ok=True)
+    with (path / "Cargo.toml").open("w") as f:
+        f.write(f'[package]\nname = "{name}"\nversion = "0.1.0"\n')
+
+
+@pytest.fixture
+def workspace_with_fixtures(pargit):
+    ws = pargit.repo.into_rust_workspace()
+    with ws.toml_path.open("a") as f:
+        f.write('exclude = ["tests/fixtures/*"]\n')
+    _make_fixture_manifest(pargit.repo.path / "tests" / "fixtures" / "fixture", "fixture")
+    ws.cargo_check()
+    pargit.repo.commit_all_changes()
+    return ws
+
+
+def _fixture_version(repo):
+    return repo.get_toml_version("tests/fixtures/fixture/Cargo.toml")
+
+
+@pytest.mark.parametrize(
+    "discovery, bumped", [("walk", False), ("index", True), ("workspace", False)]
+)
+def test_discovery_of_ignored_tracked_manifests(
+    pargit, workspace_with_fixtures, discovery, bumped
+):
+    pargit.repo.configure_pargit({"project": {"discovery": discovery}})
+    with (pargit.repo.path / ".gitignore").open("a") as f:
+        f.write("vendor/\n")
+    pargit.repo.commit_all_changes()
+    # only the index sees a manifest that is tracked despite being ignored
+    _make_fixture_manifest(pargit.repo.path / "vendor" / "ignored", "ignored")
+    pargit.repo.shell("git add -f vendor/ignored/Cargo.toml")
+    pargit.repo.commit_all_changes()
+
+    pargit.version_bump("minor")
+
+    for crate in workspace_with_fixtures.iter_rust_workspace_crates():
+        assert toml.load(crate.toml_path.open())["package"]["version"] == "0.2.0"
+    assert pargit.repo.get_toml_version("vendor/ignored/Cargo.toml") == (
+        "0.2.0" if bumped else "0.1.0"
+    )
+
+
+def test_workspace_discovery_skips_excluded_members(pargit, workspace_with_fixtures):
+    pargit.repo.configure_pargit({"project": {"discovery": "workspace"}})
+    pargit.repo.commit_all_changes()
+
+    pargit.version_bump("minor")
+
+    assert _fixture_version(pargit.repo) == "0.1.0"
+
+
+@pytest.mark.parametrize("discovery", ["index", "workspace"])
+def test_discovery_large_tree(pargit, workspace_with_fixtures, discovery):
+    # a synthetic node_modules with thousands of directories and stray manifests, which these modes never walk
+    node_modules = pargit.repo.path / "node_modules"
+    for i in range(200):
+        for j in range(50):
+            (node_modules / f"pkg{i}" / f"dir{j}").mkdir(parents=True)
+        _make_fixture_manifest(node_modules / f"pkg{i}", f"pkg{i}")
+    with (pargit.repo.path / ".gitignore").open("a") as f:
+        f.write("node_modules/\n")
+    pargit.repo.configure_pargit(
+        {"project": {"discovery": discovery, "compilation_command": "true"}}
+    )
+    pargit.repo.commit_all_changes()
+
+    pargit.version_bump("minor", timeout=60)
+
+    for crate in workspace_with_fixtures.iter_rust_workspace_crates():
+        assert toml.load(crate.toml_path.open())["package"]["version"] == "0.2.0"
+    assert pargit.repo.get_toml_version("node_modules/pkg0/Cargo.toml") == "0.1.0"
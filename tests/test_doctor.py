@@ -0,0 +1,47 @@
+import json
+import subprocess
+
+import pytest
+
+
+def _checks(pargit):
+    try:
+        result = pargit.doctor("--output", "json", capture=True)
+    except subprocess.CalledProcessError as e:
+        result = e
+    output = json.loads(result.stdout)
+    return result.returncode, {check["name"]: check for check in output["checks"]}
+
+
+def test_doctor_healthy_repository(pargit):
+    returncode, checks = _checks(pargit)
+    assert returncode == 0
+    assert all(check["ok"] for check in checks.values())
+
+
+def test_doctor_reports_problems(pargit, main_branch, develop_branch):
+    repo = pargit.repo
+    repo.switch_to_branch(main_branch)
+    repo.commit_change()
+    repo.create_branch("pargit-in-progress-release-0.2.0")
+    repo.shell(f"git branch --unset-upstream {develop_branch}")
+    repo.shell("git checkout --detach")
+
+    returncode, checks = _checks(pargit)
+    assert returncode == 14
+    assert checks["HEAD is on a branch"]["problem"] == "HEAD is detached"
+    assert not checks[f"{main_branch} is merged into {develop_branch}"]["ok"]
+    assert not checks["No leftover temporary branches"]["ok"]
+    upstream = checks[f"{develop_branch} tracks an up to date remote branch"]
+    assert "--set-upstream-to" in upstream["hint"]
+    assert checks[f"{main_branch} tracks an up to date remote branch"]["ok"]
+
+
+def test_doctor_runs_without_develop_branch(pargit, main_branch, develop_branch):
+    pargit.repo.switch_to_branch(main_branch)
+    pargit.repo.delete_branch(develop_branch)
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.doctor(capture=True)
+    assert caught.value.returncode == 14
+    assert f"✘ {develop_branch} branch exists" in caught.value.stdout
+    assert "pargit configure" in caught.value.stdout
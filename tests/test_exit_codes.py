@@ -0,0 +1,54 @@
+import json
+import subprocess
+
+import pytest
+
+
+def _fail(command, *args):
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        command(*args, capture=True)
+    return caught.value
+
+
+def test_exit_code_dirty_tree(pargit):
+    (pargit.repo.path / "dirt").write_text("dirt")
+    pargit.repo.shell("git add dirt")
+    assert _fail(pargit.feature_start, "blap").returncode == 10
+
+
+def test_exit_code_behind_remote(pargit, develop_branch):
+    pargit.repo.shell("git commit -m test --allow-empty")
+    pargit.repo.shell(f"git push origin {develop_branch}")
+    pargit.repo.shell("git reset --hard HEAD^")
+    assert _fail(pargit.release_version_minor, "--no-pull").returncode == 11
+
+
+def test_exit_code_push_rejected(pargit, remote_repo, main_branch):
+    hook = remote_repo.path / "hooks" / "pre-receive"
+    hook.write_text(
+        "#!/bin/sh\n"
+        f'while read old new ref; do [ "$ref" = refs/heads/{main_branch} ] && exit 1; done\n'
+        "exit 0\n"
+    )
+    hook.chmod(0o755)
+    pargit.repo.tag("0.1.0")
+    assert _fail(pargit.release_version_minor).returncode == 12
+    assert "0.2.0" not in pargit.repo.tags()
+
+
+def test_exit_code_tag_exists(pargit):
+    pargit.repo.tag("0.2.0")
+    assert _fail(pargit.release_start, "0.2.0").returncode == 13
+
+
+def test_exit_code_check_failed(pargit):
+    pargit.repo.tag("0.2.0")
+    assert _fail(pargit.release_start, "0.1.0").returncode == 14
+
+
+def test_exit_code_config_invalid(pargit):
+    (pargit.repo.path / ".pargit.toml").write_text("tag_prefix = [")
+    pargit.repo.commit_all_changes()
+    error = _fail(pargit.feature_start, "blap", "--output", "json")
+    assert error.returncode == 15
+    assert json.loads(error.stdout)["error"]["code"] == "config_invalid"
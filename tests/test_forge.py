@@ -0,0 +1,54 @@
+import json
+
+import pytest
+
+
+@pytest.fixture
+def forge_server(http_server):
+    http_server.status = 201
+    http_server.response = {
+        "id": 1,
+        "upload_url": f"{http_server.url}/uploads/releases/1/assets{{?name,label}}",
+    }
+    return http_server
+
+
+@pytest.fixture
+def forge_pargit(pargit, forge_server):
+    pargit.repo.configure_pargit(
+        {
+            "forge": {
+                "provider": "github",
+                "base_url": forge_server.url,
+                "repository": "owner/repo",
+                "assets": ["dist/*.txt"],
+            }
+        }
+    )
+    (pargit.repo.path / "CHANGELOG.md").write_text(
+        "# Changelog\n\n## 0.2.0\n* New things\n\n## 0.1.0\n* Old things\n"
+    )
+    pargit.repo.commit_all_changes()
+    pargit.repo.tag("0.1.0")
+    pargit.env["PARGIT_FORGE_TOKEN"] = "secret"
+    return pargit
+
+
+def test_forge_release_created(forge_pargit, forge_server):
+    (forge_pargit.repo.path / "dist").mkdir()
+    (forge_pargit.repo.path / "dist" / "artifact.txt").write_text("data")
+    forge_pargit.release_version_minor()
+
+    [(path, headers, body), (upload_path, upload_headers, upload_body)] = forge_server.requests
+    assert path == "/repos/owner/repo/releases"
+    assert headers["Authorization"] == "Bearer secret"
+    assert json.loads(body) == {"tag_name": "0.2.0", "name": "0.2.0", "body": "* New things"}
+    assert upload_path == "/uploads/releases/1/assets?name=artifact.txt"
+    assert upload_body == b"data"
+
+
+def test_forge_failure_does_not_fail_release(forge_pargit, forge_server):
+    forge_server.status = 500
+    forge_pargit.release_version_minor()
+    assert len(forge_server.requests) == 1
+    assert "0.2.0" in forge_pargit.repo.shell_output("git ls-remote --tags origin")
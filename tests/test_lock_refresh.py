@@ -0,0 +1,46 @@
+import pytest
+import toml
+
+
+@pytest.mark.parametrize("lock_refresh", ["rewrite", "cargo_update"])
+def test_bump_refreshes_lock_without_compiling(pargit, lock_refresh):
+    crate = pargit.repo.into_rust_project()
+    crate.cargo_check()
+    pargit.repo.configure_pargit(
+        {"project": {"lock_refresh": lock_refresh, "compilation_command": "false"}}
+    )
+    pargit.repo.commit_all_changes()
+
+    pargit.version_bump("minor")
+
+    assert pargit.repo.get_cargo_toml_version() == "0.2.0"
+    with (pargit.repo.path / "Cargo.lock").open() as f:
+        assert 'version = "0.2.0"' in f.read()
+    pargit.repo.shell("cargo check --locked")
+
+
+@pytest.mark.parametrize("root_package", [False, True])
+def test_rewrite_lock_workspace_inherited_versions(pargit, root_package):
+    ws = pargit.repo.into_rust_workspace()
+    ws.update_toml_file({"workspace": {"package": {"version": "1.0.0"}}})
+    if root_package:
+        ws.update_toml_file(
+            {"package": {"name": "rootpkg", "version": {"workspace": True}}}
+        )
+        (pargit.repo.path / "src").mkdir()
+        (pargit.repo.path / "src" / "main.rs").write_text("fn main() {}\n")
+    for crate in ws.iter_rust_workspace_crates():
+        with crate.toml_path.open("w") as f:
+            f.write(
+                f'[package]\nname = "{crate.path.name}"\nversion.workspace = true\n'
+            )
+    ws.cargo_check()
+    pargit.repo.configure_pargit({"project": {"lock_refresh": "rewrite"}})
+    pargit.repo.commit_all_changes()
+
+    pargit.version_bump("minor")
+
+    pargit.repo.shell("cargo check --workspace --locked")
+    with (pargit.repo.path / "Cargo.lock").open() as f:
+        locked = {p["name"]: p["version"] for p in toml.load(f)["package"]}
+    assert set(locked.values()) == {"1.1.0"}
@@ -0,0 +1,54 @@
+import json
+
+
+def _main_branch(pargit):
+    result = pargit.config_show("--output", "json", capture=True)
+    [entry] = [
+        entry
+        for entry in json.loads(result.stdout)["config"]
+        if entry["key"] == "main_branch_name"
+    ]
+    return entry["value"], entry["origin"]
+
+
+def _unconfigure_main_branch(pargit, develop_branch):
+    (pargit.repo.path / ".pargit.toml").write_text(
+        f'develop_branch_name = "{develop_branch}"\n'
+    )
+    pargit.repo.commit_all_changes()
+
+
+def test_main_branch_detected_from_remote_head(pargit, main_branch, develop_branch):
+    _unconfigure_main_branch(pargit, develop_branch)
+    pargit.repo.shell(
+        f"git symbolic-ref refs/remotes/origin/HEAD refs/remotes/origin/{main_branch}"
+    )
+    assert _main_branch(pargit) == (main_branch, "detected from origin/HEAD")
+
+
+def test_main_branch_detected_from_local_branches(pargit, main_branch, develop_branch):
+    _unconfigure_main_branch(pargit, develop_branch)
+    pargit.repo.shell(f"git branch -m {main_branch} main")
+    assert _main_branch(pargit) == ("main", "detected from local branches")
+
+
+def test_configured_main_branch_differing_from_remote_head_warns(
+    pargit, main_branch, develop_branch
+):
+    pargit.repo.shell(f"git push origin {main_branch}:trunk")
+    pargit.repo.shell("git fetch origin")
+    pargit.repo.shell("git symbolic-ref refs/remotes/origin/HEAD refs/remotes/origin/trunk")
+    result = pargit.config_show(capture=True)
+    assert "the default branch of origin is trunk" in result.stderr
+
+
+def test_remote_head_on_develop_branch_ignored(pargit, main_branch, develop_branch):
+    pargit.repo.shell(
+        f"git symbolic-ref refs/remotes/origin/HEAD refs/remotes/origin/{develop_branch}"
+    )
+    result = pargit.config_show(capture=True)
+    assert "the default branch of origin" not in result.stderr
+
+    _unconfigure_main_branch(pargit, develop_branch)
+    pargit.repo.shell(f"git branch -m {main_branch} main")
+    assert _main_branch(pargit) == ("main", "detected from local branches")
@@ -0,0 +1,63 @@
+import subprocess
+
+import pytest
+
+
+@pytest.fixture
+def templated_pargit(pargit):
+    pargit.repo.configure_pargit(
+        {
+            "messages": {
+                "bump_commit": "chore(release): bump {previous_version} -> {version}",
+                "release_merge": "chore(release): merge {branch} ({commit_count} commits)",
+                "feature_merge": "feat: merge {name}",
+                "tag": "Release {version}\n\nIt's {kind} {tag}",
+            }
+        }
+    )
+    pargit.repo.commit_all_changes()
+    return pargit
+
+
+def _subjects(repo, ref):
+    return repo.shell_output(f"git log --format=%s {ref}").splitlines()
+
+
+def test_release_message_templates(templated_pargit, main_branch, develop_branch):
+    repo = templated_pargit.repo
+    repo.into_rust_project()
+    repo.switch_to_branch(main_branch)
+    repo.commit_change()
+    repo.shell(f"git push origin {main_branch}")
+    repo.switch_to_branch(develop_branch)
+
+    templated_pargit.release_version_minor()
+
+    subjects = _subjects(repo, main_branch)
+    assert "chore(release): bump 0.1.0 -> 0.2.0" in subjects
+    assert any(
+        s.startswith("chore(release): merge release/0.2.0 (") for s in subjects
+    )
+    assert not any("temporary" in s for s in subjects)
+    assert "Release 0.2.0\n\nIt's release 0.2.0" in repo.shell_output(
+        "git tag -l --format='%(contents)' 0.2.0"
+    )
+
+
+def test_feature_merge_message_template(templated_pargit, develop_branch):
+    repo = templated_pargit.repo
+    templated_pargit.feature_start("blap")
+    repo.commit_change()
+    repo.switch_to_branch(develop_branch)
+    repo.commit_change()
+    templated_pargit.feature_finish("blap")
+    assert _subjects(repo, develop_branch)[0] == "feat: merge blap"
+
+
+def test_unknown_placeholder_fails(pargit):
+    pargit.repo.configure_pargit({"messages": {"feature_merge": "{nope}"}})
+    pargit.repo.commit_all_changes()
+    pargit.feature_start("blap")
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.feature_finish("blap", capture=True)
+    assert "Unknown placeholder {nope}" in caught.value.stderr
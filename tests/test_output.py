@@ -0,0 +1,35 @@
+import json
+import subprocess
+
+import pytest
+
+
+def _json(result):
+    return json.loads(result.stdout)
+
+
+def test_json_output_release_version(pargit):
+    pargit.repo.into_rust_project()
+    output = _json(pargit.release_version_minor("--output", "json", capture=True))
+    assert output["ok"]
+    assert output["error"] is None
+    assert output["release"] == {"name": "0.2.0", "version": "0.2.0", "tag": "0.2.0"}
+    assert output["created_branches"] == ["release/0.2.0"]
+    assert output["bumped_files"] == ["Cargo.toml"]
+    assert "refs/tags/0.2.0" in output["pushed_refs"]
+
+
+def test_json_output_suppresses_logging(pargit):
+    result = pargit.feature_start("blap", "--output", "json", capture=True)
+    assert result.stderr == ""
+    assert _json(result)["created_branches"] == ["feature/blap"]
+
+
+def test_json_output_error(pargit):
+    pargit.feature_start("blap")
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.feature_start("blap", "--output", "json", capture=True)
+    output = _json(caught.value)
+    assert not output["ok"]
+    assert output["error"]["code"]
+    assert "already in progress" in output["error"]["message"]
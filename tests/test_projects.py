@@ -0,0 +1,75 @@
+import subprocess
+
+import pytest
+
+from conftest import make_rust_project
+
+
+@pytest.fixture
+def multi_project(pargit):
+    cli_path = pargit.repo.path / "cli"
+    cli_path.mkdir()
+    make_rust_project(cli_path, "cli")
+    (pargit.repo.path / "sdk").mkdir()
+    with (pargit.repo.path / "sdk" / "setup.py").open("w") as f:
+        f.write("")
+    with (pargit.repo.path / ".gitignore").open("w") as f:
+        f.write("target/\n")
+    pargit.repo.configure_pargit(
+        {
+            "tag_prefix": "v",
+            "projects": [
+                {"name": "cli", "subpath": "cli"},
+                {"name": "sdk", "subpath": "sdk", "type": "generic"},
+            ],
+        }
+    )
+    subprocess.check_call("cargo check", shell=True, cwd=cli_path)
+    pargit.repo.commit_all_changes()
+    pargit.repo.tag("sdk-v1.0.0")
+    return pargit
+
+
+def test_multiple_projects_require_selection(multi_project):
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        multi_project.release_version("minor", capture=True)
+    assert "--project" in caught.value.stderr
+
+
+def test_release_generic_project(multi_project):
+    multi_project.release_version("minor", "--project sdk")
+    assert multi_project.repo.tags() == {"sdk-v1.0.0", "sdk-v1.1.0"}
+    assert multi_project.repo.get_toml_version("cli/Cargo.toml") == "0.1.0"
+
+
+def test_release_rust_project(multi_project):
+    multi_project.release_version("minor", "--project cli")
+    assert "cli-v0.2.0" in multi_project.repo.tags()
+    assert multi_project.repo.get_toml_version("cli/Cargo.toml") == "0.2.0"
+
+
+def test_release_branch_namespaced(multi_project):
+    multi_project.release_start("major", "--project sdk")
+    assert multi_project.repo.current_branch() == "release/sdk-2.0.0"
+    multi_project.release_finish("--project sdk")
+    assert "sdk-v2.0.0" in multi_project.repo.tags()
+
+
+def test_release_generic_project_with_own_tag_prefix(multi_project):
+    multi_project.repo.configure_pargit(
+        {
+            "projects": [
+                {"name": "cli", "subpath": "cli"},
+                {
+                    "name": "sdk",
+                    "subpath": "sdk",
+                    "type": "generic",
+                    "tag_prefix": "release-",
+                },
+            ],
+        }
+    )
+    multi_project.repo.commit_all_changes()
+    multi_project.repo.tag("sdk-release-1.4.0")
+    multi_project.release_version("minor", "--project sdk")
+    assert "sdk-release-1.5.0" in multi_project.repo.tags()
@@ -0,0 +1,100 @@
+import subprocess
+
+import pytest
+
+
+@pytest.fixture
+def protected_pargit(pargit):
+    pargit.repo.configure_pargit({"protected_main_branch": True})
+    pargit.repo.commit_all_changes()
+    return pargit
+
+
+def _remote_refs(remote_repo):
+    return {
+        line.split()[1]
+        for line in remote_repo.shell_output("git show-ref").splitlines()
+    }
+
+
+def _merge_pull_request(remote_repo, tmpdir, main_branch, branch, squash=False):
+    reviewer = remote_repo.clone_to(tmpdir / "reviewer")
+    reviewer.switch_to_branch(main_branch)
+    if squash:
+        reviewer.shell(f"git merge --squash origin/{branch}")
+        reviewer.shell("git commit -m 'Release (#1)'")
+    else:
+        reviewer.shell(f"git merge --no-ff origin/{branch} -m 'Merge pull request'")
+    reviewer.shell(f"git push origin {main_branch}")
+    return reviewer
+
+
+def test_protected_release_pushes_branch_and_completes(
+    protected_pargit, remote_repo, tmpdir, main_branch, develop_branch
+):
+    repo = protected_pargit.repo
+    main_before = remote_repo.shell_output(f"git rev-parse {main_branch}")
+    change = repo.commit_change()
+
+    protected_pargit.release_version("0.1.0")
+
+    temp_branch = "pargit-in-progress-release-0.1.0"
+    assert f"refs/heads/{temp_branch}" in _remote_refs(remote_repo)
+    assert remote_repo.shell_output(f"git rev-parse {main_branch}") == main_before
+    assert "0.1.0" not in repo.tags()
+    assert repo.current_branch() == "release/0.1.0"
+
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        protected_pargit.release_complete(capture=True)
+    assert "has not been merged" in caught.value.stderr
+
+    _merge_pull_request(remote_repo, tmpdir, main_branch, temp_branch)
+    protected_pargit.release_complete()
+
+    assert "0.1.0" in repo.tags()
+    assert "refs/tags/0.1.0" in _remote_refs(remote_repo)
+    assert f"refs/heads/{temp_branch}" not in _remote_refs(remote_repo)
+    assert repo.branches() == {main_branch, develop_branch}
+    assert repo.current_branch() == develop_branch
+    assert change.exists()
+    repo.switch_to_branch(main_branch)
+    assert change.exists()
+
+
+def test_protected_release_completes_after_forge_deleted_branch(
+    protected_pargit, remote_repo, tmpdir, main_branch, develop_branch
+):
+    repo = protected_pargit.repo
+    repo.commit_change()
+    protected_pargit.release_version("0.1.0")
+
+    temp_branch = "pargit-in-progress-release-0.1.0"
+    _merge_pull_request(remote_repo, tmpdir, main_branch, temp_branch)
+    remote_repo.shell(f"git branch -D {temp_branch}")
+    protected_pargit.release_complete()
+
+    assert "refs/tags/0.1.0" in _remote_refs(remote_repo)
+    assert repo.branches() == {main_branch, develop_branch}
+    assert repo.current_branch() == develop_branch
+
+
+def test_protected_release_completes_after_squash_merge(
+    protected_pargit, remote_repo, tmpdir, main_branch, develop_branch
+):
+    repo = protected_pargit.repo
+    change = repo.commit_change()
+    protected_pargit.release_version("0.1.0")
+
+    temp_branch = "pargit-in-progress-release-0.1.0"
+    reviewer = _merge_pull_request(
+        remote_repo, tmpdir, main_branch, temp_branch, squash=True
+    )
+    squashed = reviewer.shell_output("git rev-parse HEAD")
+    reviewer.commit_change()
+    reviewer.shell(f"git push origin {main_branch}")
+    protected_pargit.release_complete()
+
+    assert remote_repo.shell_output("git rev-parse 0.1.0^{commit}") == squashed
+    assert repo.branches() == {main_branch, develop_branch}
+    assert repo.current_branch() == develop_branch
+    assert change.exists()
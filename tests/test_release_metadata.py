@@ -0,0 +1,54 @@
+import json
+import subprocess
+
+import pytest
+import toml
+
+from conftest import Pargit
+
+
+def test_release_records_metadata(pargit, remote_repo, develop_branch, tmpdir):
+    pargit.release_version("0.2.0")
+    note = pargit.repo.shell_output("git notes --ref=pargit show 0.2.0^{commit}")
+    metadata = toml.loads(note)
+    assert metadata["tag"] == "0.2.0"
+    assert metadata["kind"] == "release"
+    assert metadata["released_by"]
+    assert "clean_after_compilation" in metadata["checks"]
+
+    # the metadata is pushed, and can be read from other clones
+    clone = remote_repo.clone_to(tmpdir / "another_clone")
+    clone.switch_to_branch(develop_branch)
+    result = Pargit(pargit.binary, clone).release_info(
+        "0.2.0", "--output", "json", capture=True
+    )
+    assert json.loads(result.stdout)["metadata"] == metadata
+
+
+def test_release_info_without_metadata(pargit):
+    pargit.repo.tag("0.1.0")
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.release_info("0.1.0", capture=True)
+    assert "No release metadata recorded for 0.1.0" in caught.value.stderr
+
+
+def test_unpushed_metadata_reported_and_kept(pargit, remote_repo, develop_branch, tmpdir):
+    hook = remote_repo.path / "hooks" / "update"
+    with hook.open("w") as f:
+        f.write('#!/bin/sh\n[ "$1" != refs/notes/pargit ]\n')
+    hook.chmod(0o755)
+    result = pargit.release_version("0.1.0", capture=True)
+    assert "git push origin refs/notes/pargit" in result.stderr
+    hook.remove()
+
+    # a release from another clone pushes its own metadata in the meantime
+    clone = remote_repo.clone_to(tmpdir / "another_clone")
+    clone.switch_to_branch(develop_branch)
+    clone.commit_change()
+    Pargit(pargit.binary, clone).release_version("0.2.0")
+
+    result = pargit.release_info("0.1.0", capture=True)
+    assert "was never pushed" in result.stderr
+    assert "Released by" in result.stdout
+    pargit.repo.shell("git push origin refs/notes/pargit")
+    assert "was never pushed" not in pargit.release_info("0.1.0", capture=True).stderr
@@ -0,0 +1,34 @@
+import subprocess
+
+import pytest
+
+
+@pytest.fixture
+def teammate_tag(pargit, remote_repo, tmpdir, main_branch):
+    other = remote_repo.clone_to(tmpdir / "teammate")
+    other.shell(
+        "git -c user.name=Teammate -c user.email=teammate@example.com "
+        f"tag -a -m 0.2.0 0.2.0 origin/{main_branch}"
+    )
+    other.shell("git push origin 0.2.0")
+    pargit.repo.tag("0.1.0")
+    return "0.2.0"
+
+
+def test_release_start_fails_when_tag_exists_on_remote(
+    pargit, teammate_tag, main_branch, develop_branch
+):
+    assert teammate_tag not in pargit.repo.tags()
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.release_start("minor", capture=True)
+    stderr = caught.value.stderr
+    assert "already exists on remote" in stderr
+    assert "Teammate <teammate@example.com>" in stderr
+    assert pargit.repo.branches() == {main_branch, develop_branch}
+    assert teammate_tag not in pargit.repo.tags()
+
+
+def test_release_version_exact_fails_when_tag_exists_on_remote(pargit, teammate_tag):
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.release_version(teammate_tag, "--no-pull", capture=True)
+    assert "already exists" in caught.value.stderr
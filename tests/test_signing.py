@@ -0,0 +1,59 @@
+import subprocess
+
+import pytest
+
+
+@pytest.fixture
+def signing_pargit(pargit):
+    pargit.repo.configure_pargit({"sign_tags": True, "sign_commits": True})
+    pargit.repo.commit_all_changes()
+    pargit.repo.shell("git config gpg.format ssh")
+    return pargit
+
+
+@pytest.fixture
+def ssh_key(signing_pargit, tmpdir):
+    key_path = tmpdir / "signing_key"
+    subprocess.check_call(f"ssh-keygen -q -t ed25519 -N '' -f {key_path}", shell=True)
+    signing_pargit.repo.shell(f"git config user.signingkey {key_path}")
+    return key_path
+
+
+def test_signing_without_key_fails_early(signing_pargit, main_branch, develop_branch):
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        signing_pargit.release_version_minor(capture=True)
+    assert "no signing key is available" in caught.value.stderr
+    assert signing_pargit.repo.branches() == {main_branch, develop_branch}
+    assert signing_pargit.repo.tags() == set()
+
+
+def test_signed_release(signing_pargit, ssh_key, main_branch, develop_branch):
+    signing_pargit.repo.switch_to_branch(main_branch)
+    signing_pargit.repo.commit_change()
+    signing_pargit.repo.shell(f"git push origin {main_branch}")
+    signing_pargit.repo.switch_to_branch(develop_branch)
+    signing_pargit.repo.commit_change()
+
+    signing_pargit.release_version("0.1.0")
+
+    assert "SSH SIGNATURE" in signing_pargit.repo.shell_output("git cat-file tag 0.1.0")
+    for branch in (main_branch, develop_branch):
+        assert "gpgsig" in signing_pargit.repo.shell_output(
+            f"git cat-file commit {branch}"
+        )
+
+
+def test_signed_release_never_fast_forwards(
+    signing_pargit, ssh_key, main_branch, develop_branch
+):
+    signing_pargit.repo.commit_change()
+
+    signing_pargit.release_version("0.1.0")
+
+    for branch in (main_branch, develop_branch):
+        assert "gpgsig" in signing_pargit.repo.shell_output(
+            f"git cat-file commit {branch}"
+        )
+    assert signing_pargit.repo.shell_output(
+        f"git rev-parse {main_branch}"
+    ) == signing_pargit.repo.shell_output(f"git rev-parse origin/{main_branch}")
@@ -0,0 +1,39 @@
+import subprocess
+
+import pytest
+
+
+@pytest.fixture
+def tagged_ahead(pargit):
+    pargit.repo.into_rust_project()
+    pargit.repo.tag("0.5.0")
+    return pargit
+
+
+@pytest.mark.parametrize("spec", ["minor", "0.4.0"])
+def test_release_lower_than_existing_tag_fails(tagged_ahead, spec):
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        tagged_ahead.release_version(spec, capture=True)
+    assert "not greater than existing tag 0.5.0" in caught.value.stderr
+    assert tagged_ahead.repo.tags() == {"0.5.0"}
+
+
+def test_manifest_disagrees_with_latest_tag_fails(tagged_ahead):
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        tagged_ahead.release_start("0.6.0", capture=True)
+    assert "does not match the latest tag 0.5.0" in caught.value.stderr
+
+
+def test_release_force_overrides_version_checks(tagged_ahead):
+    tagged_ahead.release_version("minor", "--force")
+    assert tagged_ahead.repo.tags() == {"0.5.0", "0.2.0"}
+
+
+def test_manifest_ahead_by_prerelease(pargit):
+    crate = pargit.repo.into_rust_project()
+    pargit.repo.tag("0.1.0")
+    crate.update_toml_file({"package": {"version": "0.2.0-alpha.1"}})
+    crate.cargo_check()
+    pargit.repo.commit_all_changes()
+    pargit.release_version("0.2.0")
+    assert "0.2.0" in pargit.repo.tags()
@@ -0,0 +1,58 @@
+import json
+
+
+def _payloads(server):
+    return [json.loads(body) for _, _, body in server.requests]
+
+
+def _configure(pargit, server, **kwargs):
+    webhook = {"url": f"{server.url}/hook", **kwargs}
+    pargit.repo.configure_pargit({"webhooks": [webhook]})
+    pargit.repo.commit_all_changes()
+    pargit.repo.tag("0.1.0")
+
+
+def test_webhook_release_finish(pargit, http_server):
+    _configure(pargit, http_server)
+    pargit.release_version_minor()
+    [payload] = _payloads(http_server)
+    assert payload["event"] == "release_finish"
+    assert payload["kind"] == "release"
+    assert payload["version"] == payload["tag"] == "0.2.0"
+    assert payload["commit"] == pargit.repo.shell_output("git rev-parse 0.2.0^{commit}").strip()
+    assert payload["remote"]
+    assert payload["user"]
+
+
+def test_webhook_feature_events(pargit, http_server):
+    _configure(pargit, http_server)
+    pargit.feature_start("a")
+    pargit.repo.commit_change()
+    pargit.feature_finish()
+    pargit.feature_start("b")
+    pargit.feature_delete()
+    assert [(p["event"], p["name"]) for p in _payloads(http_server)] == [
+        ("feature_finish", "a"),
+        ("feature_delete", "b"),
+    ]
+
+
+def test_webhook_event_filter(pargit, http_server):
+    _configure(pargit, http_server, events=["delete"])
+    pargit.feature_start("a")
+    pargit.repo.commit_change()
+    pargit.feature_finish()
+    assert http_server.requests == []
+
+
+def test_webhook_retries_and_failures_are_not_fatal(pargit, http_server):
+    _configure(pargit, http_server, retries=1)
+    http_server.statuses = [500, 200]
+    pargit.feature_start("a")
+    pargit.feature_delete()
+    assert len(http_server.requests) == 2
+
+    http_server.statuses = [500, 500]
+    pargit.feature_start("b")
+    pargit.feature_delete()
+    assert len(http_server.requests) == 4
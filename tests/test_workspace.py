@@ -1,4 +1,5 @@
 import toml
+import subprocess
 import pytest
 
 
@@ -53,3 +54,49 @@ def test_workspace_bump_workspace_version_override(pargit, unquote_dotted):
 
     for path, contents in prev_cargo_toml_contents.items():
         assert path.open().read() == contents
+
+
+@pytest.fixture
+def diverged_workspace(pargit):
+    ws = pargit.repo.into_rust_workspace()
+    (crate1, crate2) = sorted(ws.iter_rust_workspace_crates(), key=lambda c: c.path)
+    crate2.update_toml_file({"package": {"version": "0.5.0"}})
+    ws.cargo_check()
+    pargit.repo.commit_all_changes()
+    return ws
+
+
+def _crate_versions(ws):
+    return {
+        crate.path.name: toml.load(crate.toml_path.open())["package"]["version"]
+        for crate in ws.iter_rust_workspace_crates()
+    }
+
+
+def test_workspace_bump_diverged_non_interactive_fails(pargit, diverged_workspace):
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        pargit.non_interactive().version_bump("minor", capture=True)
+    stderr = caught.value.stderr
+    assert "--package" in stderr
+    assert "crate1" in stderr
+    assert "crate2" in stderr
+
+
+@pytest.mark.parametrize(
+    "flag, expected",
+    [
+        ("--package crate2", {"crate1": "0.1.0", "crate2": "0.6.0"}),
+        ("--version-file crate1/Cargo.toml", {"crate1": "0.2.0", "crate2": "0.5.0"}),
+        ("--all-version-files", {"crate1": "0.2.0", "crate2": "0.6.0"}),
+    ],
+)
+def test_workspace_bump_diverged_with_selection(
+    pargit, diverged_workspace, flag, expected
+):
+    pargit.non_interactive().version_bump("minor", flag)
+    assert _crate_versions(diverged_workspace) == expected
+
+
+def test_workspace_release_diverged_with_package(pargit, diverged_workspace):
+    pargit.non_interactive().release_version("minor", "--package crate2")
+    assert "0.6.0" in pargit.repo.tags()
@@ -0,0 +1,48 @@
+import subprocess
+
+import pytest
+
+
+@pytest.fixture
+def released(pargit):
+    pargit.repo.into_rust_project()
+    pargit.repo.tag("0.1.0")
+    pargit.release_version("minor")
+    assert "0.2.0" in pargit.repo.tags()
+    return pargit
+
+
+def _remote_tags(repo):
+    return {
+        line.split("refs/tags/")[1]
+        for line in repo.shell_output("git ls-remote --tags origin").splitlines()
+        if not line.endswith("^{}")
+    }
+
+
+def test_yank_replaces_tag(released):
+    repo = released.repo
+    commit = repo.shell_output("git rev-parse 0.2.0^{commit}")
+    released.release_yank("0.2.0")
+    assert repo.tags() == {"0.1.0", "yanked/0.2.0"}
+    assert repo.shell_output("git rev-parse yanked/0.2.0^{commit}") == commit
+    assert "0.2.0" not in _remote_tags(repo)
+    assert "yanked/0.2.0" in _remote_tags(repo)
+
+
+def test_yanked_version_not_reused(released):
+    released.release_yank("0.2.0", "--revert")
+    assert released.repo.get_cargo_toml_version() == "0.1.0"
+
+    with pytest.raises(subprocess.CalledProcessError) as caught:
+        released.release_version("0.2.0", capture=True)
+    assert "was yanked" in caught.value.stderr
+
+    released.release_version("minor")
+    assert "0.3.0" in released.repo.tags()
+
+
+def test_yank_unknown_version_fails(released):
+    with pytest.raises(subprocess.CalledProcessError):
+        released.release_yank("0.9.0")
+    assert released.repo.tags() == {"0.1.0", "0.2.0"}